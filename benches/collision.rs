@@ -0,0 +1,72 @@
+//! 空間ハッシュと総当たりの衝突判定の比較
+//!
+//! `cargo bench --bench collision` で実行する。
+
+use actor_game::actor::{
+    Actor, ActorStorage, CircleComponent, CollisionBody, CollisionSystem, Vector2,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// 1体あたりの面積が一定になるように円を持つアクターをばらまく
+fn make_actors(count: usize) -> ActorStorage {
+    let mut rng = StdRng::seed_from_u64(count as u64);
+    let side = (count as f32).sqrt() * 100.0;
+    let mut actors = ActorStorage::new();
+    for _ in 0..count {
+        let mut actor = Actor::new();
+        actor.set_position(Vector2::new(
+            rng.gen_range(0.0..side),
+            rng.gen_range(0.0..side),
+        ));
+        actor.add_component(Box::new(CircleComponent::new(rng.gen_range(10.0..40.0))));
+        actors.insert(actor);
+    }
+    actors
+}
+
+/// 処理を繰り返し実行して1回あたりの平均時間を返す
+fn measure<F: FnMut() -> usize>(iterations: u32, mut f: F) -> (Duration, usize) {
+    let mut found = f(); // ウォームアップ
+    let start = Instant::now();
+    for _ in 0..iterations {
+        found = f();
+    }
+    (start.elapsed() / iterations, found)
+}
+
+fn main() {
+    for &count in &[100, 1_000, 10_000] {
+        let actors = make_actors(count);
+        let bodies: Vec<CollisionBody> = actors
+            .iter()
+            .filter_map(|(actor_id, actor)| {
+                actor
+                    .get_component::<CircleComponent>()
+                    .map(|circle| CollisionBody {
+                        actor_id,
+                        owner: actor.state(),
+                        circle,
+                    })
+            })
+            .collect();
+        let iterations = (1_000_000 / count as u32).clamp(3, 1_000);
+
+        let mut system = CollisionSystem::new();
+        let (hashed, hashed_found) = measure(iterations, || system.find_collisions(&bodies).len());
+        let (brute, brute_found) = measure(iterations, || {
+            system.find_collisions_brute_force(&bodies).len()
+        });
+        assert_eq!(hashed_found, brute_found);
+
+        println!(
+            "{:>6} bodies: spatial hash {:>10.3?}  brute force {:>10.3?}  ({} collisions, x{:.1})",
+            count,
+            hashed,
+            brute,
+            hashed_found,
+            brute.as_secs_f64() / hashed.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! ウィンドウもGPUも使わずにゲームを進める速さの計測
+//!
+//! `cargo bench --bench simulation` で実行する。
+
+use actor_game::actor::game::Game;
+use actor_game::actor::{Vector2, WaveConfig};
+use std::time::Instant;
+use winit::event::VirtualKeyCode;
+
+/// 1回の更新で進める時間
+const STEP_TIME: f32 = 1.0 / 60.0;
+/// 毎回同じように進めるためのシード
+const SEED: u64 = 12345;
+
+/// タイトル画面から始めて、フェードが終わるまで進める
+fn start_playing(game: &mut Game) {
+    game.step(&[VirtualKeyCode::Return], STEP_TIME);
+    for _ in 0..60 {
+        game.step(&[], STEP_TIME);
+    }
+}
+
+/// 回りながら撃ち、ときどき加速する
+fn play(game: &mut Game, frames: u32) {
+    for frame in 0..frames {
+        let keys: &[VirtualKeyCode] = if frame % 30 < 20 {
+            &[VirtualKeyCode::D, VirtualKeyCode::Space]
+        } else {
+            &[VirtualKeyCode::W]
+        };
+        game.step(keys, STEP_TIME);
+    }
+}
+
+fn main() {
+    for &frames in &[1_000, 10_000] {
+        let mut game = Game::headless(Vector2::new(1024.0, 768.0), WaveConfig::default(), SEED);
+        start_playing(&mut game);
+
+        let start = Instant::now();
+        play(&mut game, frames);
+        let elapsed = start.elapsed();
+
+        let stats = game.world().map(|world| world.stats());
+        println!(
+            "{:>6} frames: {:>10.3?} ({:>8.3?}/frame)  {:?}",
+            frames,
+            elapsed,
+            elapsed / frames,
+            stats
+        );
+    }
+}
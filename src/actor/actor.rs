@@ -1,42 +1,248 @@
-use super::component::Component;
-use super::input_component::InputComponent;
+use super::actor_id::ActorId;
+use super::collision::CollisionEvent;
+use super::commands::Commands;
+use super::component::{Component, ComponentContext};
+use super::input_map::Action;
+use super::math::Math;
+use super::random::RandomStreams;
 use super::vector2::Vector2;
+use std::any::{Any, TypeId};
 
-/// アクターの基本構造体
-pub struct Actor {
+/// アクターの状態
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ActorStatus {
+    /// 毎フレーム更新される
+    Active,
+    /// 更新を止めている
+    Paused,
+    /// フレームの終わりにWorldから削除される
+    Dead,
+}
+
+/// アクターのトランスフォームと状態
+///
+/// コンポーネントは更新時にComponentContext経由でこれを受け取る。
+pub struct ActorState {
     /// アクターの位置
     position: Vector2,
     /// アクターの回転角度（ラジアン）
     rotation: f32,
     /// アクターのスケール
     scale: Vector2,
-    /// アクターの状態（アクティブかどうか）
-    active: bool,
+    /// 前回の更新の前の位置と回転（まだ更新していなければNone）
+    previous_transform: Option<(Vector2, f32)>,
+    /// アクターの状態
+    status: ActorStatus,
+}
+
+impl Default for ActorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActorState {
+    pub fn new() -> Self {
+        Self {
+            position: Vector2::zero(),
+            rotation: 0.0,
+            scale: Vector2::one(),
+            previous_transform: None,
+            status: ActorStatus::Active,
+        }
+    }
+
+    // Getters and Setters
+    pub fn position(&self) -> Vector2 {
+        self.position
+    }
+
+    pub fn set_position(&mut self, pos: Vector2) {
+        self.position = pos;
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rot: f32) {
+        self.rotation = rot;
+    }
+
+    pub fn scale(&self) -> Vector2 {
+        self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: Vector2) {
+        self.scale = scale;
+    }
+
+    /// 向いている方向の単位ベクトル
+    pub fn forward(&self) -> Vector2 {
+        Vector2::new(self.rotation.cos(), self.rotation.sin())
+    }
+
+    /// 更新の前に現在の位置と回転を覚えておく（描画時の補間に使う）
+    pub fn save_previous_transform(&mut self) {
+        self.previous_transform = Some((self.position, self.rotation));
+    }
+
+    /// 覚えておいた位置をずらす（画面端の折り返しで補間が画面を横切らないようにする）
+    pub fn shift_previous_position(&mut self, offset: Vector2) {
+        if let Some((position, _)) = &mut self.previous_transform {
+            *position += offset;
+        }
+    }
+
+    /// 前回の更新の前から現在までの間の位置（alphaは0.0〜1.0）
+    pub fn interpolated_position(&self, alpha: f32) -> Vector2 {
+        match self.previous_transform {
+            Some((previous, _)) => previous + (self.position - previous) * alpha,
+            None => self.position,
+        }
+    }
+
+    /// 前回の更新の前から現在までの間の回転（alphaは0.0〜1.0）
+    pub fn interpolated_rotation(&self, alpha: f32) -> f32 {
+        match self.previous_transform {
+            // -πとπをまたぐときに逆回りしないように差分を正規化する
+            Some((_, previous)) => {
+                previous + Math::normalize_angle(self.rotation - previous) * alpha
+            }
+            None => self.rotation,
+        }
+    }
+
+    pub fn status(&self) -> ActorStatus {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: ActorStatus) {
+        self.status = status;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == ActorStatus::Active
+    }
+
+    /// 死んでいないアクターの更新を再開・停止する
+    pub fn set_active(&mut self, active: bool) {
+        if self.status != ActorStatus::Dead {
+            self.status = if active {
+                ActorStatus::Active
+            } else {
+                ActorStatus::Paused
+            };
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.status == ActorStatus::Dead
+    }
+}
+
+/// アクターの基本構造体
+pub struct Actor {
+    /// アクターのトランスフォームと状態
+    state: ActorState,
     /// アクターに付属するコンポーネントのリスト
     components: Vec<Box<dyn Component>>,
+    /// ゲームの進行に関わるアクターの生成順（破片など見た目だけのアクターはNone）
+    spawn_order: Option<u64>,
+}
+
+impl Default for Actor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Actor {
     /// 新しいアクターを作成
     pub fn new() -> Self {
         Self {
-            position: Vector2::zero(),
-            rotation: 0.0,
-            scale: Vector2::one(),
-            active: true,
+            state: ActorState::new(),
             components: Vec::new(),
+            spawn_order: None,
         }
     }
 
     /// アクターを更新
-    pub fn update(&mut self, delta_time: f32) {
-        if !self.active {
+    pub fn update(
+        &mut self,
+        actor_id: ActorId,
+        delta_time: f32,
+        commands: &mut Commands,
+        random: &mut RandomStreams,
+    ) {
+        if !self.state.is_active() {
             return;
         }
 
         // 全てのコンポーネントを更新
-        for component in &mut self.components {
-            component.update(delta_time);
+        self.for_each_component(actor_id, commands, random, |component, ctx| {
+            component.update(ctx, delta_time)
+        });
+    }
+
+    /// 全てのコンポーネントに入力された操作を渡す
+    pub fn process_input(
+        &mut self,
+        actor_id: ActorId,
+        actions: &[Action],
+        commands: &mut Commands,
+        random: &mut RandomStreams,
+    ) {
+        if !self.state.is_active() {
+            return;
+        }
+
+        self.for_each_component(actor_id, commands, random, |component, ctx| {
+            component.process_input(ctx, actions)
+        });
+    }
+
+    /// 全てのコンポーネントに衝突イベントを渡す
+    pub fn on_collision(
+        &mut self,
+        actor_id: ActorId,
+        event: &CollisionEvent,
+        commands: &mut Commands,
+        random: &mut RandomStreams,
+    ) {
+        if !self.state.is_active() {
+            return;
+        }
+
+        self.for_each_component(actor_id, commands, random, |component, ctx| {
+            component.on_collision(ctx, event)
+        });
+    }
+
+    /// 付いているコンポーネントの型の一覧
+    pub fn component_types(&self) -> Vec<TypeId> {
+        self.components
+            .iter()
+            .map(|component| Any::type_id(component.as_any()))
+            .collect()
+    }
+
+    /// コンポーネントを1つずつ取り出し、他のコンポーネントと所有者の状態を渡して処理する
+    fn for_each_component<F>(
+        &mut self,
+        actor_id: ActorId,
+        commands: &mut Commands,
+        random: &mut RandomStreams,
+        mut f: F,
+    ) where
+        F: FnMut(&mut dyn Component, &mut ComponentContext),
+    {
+        for index in 0..self.components.len() {
+            let (before, rest) = self.components.split_at_mut(index);
+            let (component, after) = rest.split_first_mut().unwrap();
+            let mut ctx =
+                ComponentContext::new(actor_id, &mut self.state, before, after, commands, random);
+            f(component.as_mut(), &mut ctx);
         }
     }
 
@@ -45,37 +251,80 @@ impl Actor {
         self.components.push(component);
     }
 
+    /// 指定した型の最初のコンポーネントを取り外す
+    pub fn remove_component<T: 'static>(&mut self) -> Option<Box<dyn Component>> {
+        let index = self
+            .components
+            .iter()
+            .position(|component| component.as_ref().as_any().is::<T>())?;
+        Some(self.components.remove(index))
+    }
+
+    /// ゲームの進行に関わるアクターの生成順
+    ///
+    /// 格納場所の位置と違って見た目だけのアクターの増減で変わらないので、
+    /// 衝突の処理やチェックサムの順番に使う。
+    pub fn spawn_order(&self) -> Option<u64> {
+        self.spawn_order
+    }
+
+    pub fn set_spawn_order(&mut self, spawn_order: Option<u64>) {
+        self.spawn_order = spawn_order;
+    }
+
+    /// アクターの状態を取得
+    pub fn state(&self) -> &ActorState {
+        &self.state
+    }
+
+    /// アクターの状態を可変参照で取得
+    pub fn state_mut(&mut self) -> &mut ActorState {
+        &mut self.state
+    }
+
     // Getters and Setters
-    pub fn position(&mut self) -> Vector2 {
-        self.position
+    pub fn position(&self) -> Vector2 {
+        self.state.position()
     }
 
     pub fn set_position(&mut self, pos: Vector2) {
-        self.position = pos;
+        self.state.set_position(pos);
     }
 
-    pub fn rotation(&mut self) -> f32 {
-        self.rotation
+    pub fn rotation(&self) -> f32 {
+        self.state.rotation()
     }
 
     pub fn set_rotation(&mut self, rot: f32) {
-        self.rotation = rot;
+        self.state.set_rotation(rot);
     }
 
     pub fn scale(&self) -> Vector2 {
-        self.scale
+        self.state.scale()
     }
 
     pub fn set_scale(&mut self, scale: Vector2) {
-        self.scale = scale;
+        self.state.set_scale(scale);
+    }
+
+    pub fn interpolated_position(&self, alpha: f32) -> Vector2 {
+        self.state.interpolated_position(alpha)
+    }
+
+    pub fn interpolated_rotation(&self, alpha: f32) -> f32 {
+        self.state.interpolated_rotation(alpha)
     }
 
     pub fn is_active(&self) -> bool {
-        self.active
+        self.state.is_active()
     }
 
     pub fn set_active(&mut self, active: bool) {
-        self.active = active;
+        self.state.set_active(active);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.state.is_dead()
     }
 
     pub fn get_component<T: 'static>(&self) -> Option<&T> {
@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// アクターを指すハンドル
+///
+/// スロット番号と世代の組で、アクターが削除されるとそのスロットの世代が進む。
+/// 削除済みのアクターを指す古いハンドルは、同じスロットが再利用されても
+/// 世代が一致しないため別のアクターを指すことはない。
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ActorId {
+    index: u32,
+    generation: u32,
+}
+
+impl ActorId {
+    pub(crate) fn new(index: u32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    /// スロット番号
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// 世代
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl fmt::Display for ActorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
@@ -0,0 +1,147 @@
+use super::actor::Actor;
+use super::actor_id::ActorId;
+
+/// アクターを格納するスロット
+struct Slot {
+    /// 現在の世代（アクターが削除されるたびに進む）
+    generation: u32,
+    actor: Option<Actor>,
+}
+
+/// 世代付きハンドルでアクターを管理するストレージ
+pub struct ActorStorage {
+    slots: Vec<Slot>,
+    /// 再利用できる空きスロット
+    free_indices: Vec<u32>,
+    len: usize,
+}
+
+impl Default for ActorStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActorStorage {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_indices: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// アクターを追加してハンドルを返す
+    pub fn insert(&mut self, actor: Actor) -> ActorId {
+        self.len += 1;
+        if let Some(index) = self.free_indices.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.actor = Some(actor);
+            return ActorId::new(index, slot.generation);
+        }
+
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot {
+            generation: 0,
+            actor: Some(actor),
+        });
+        ActorId::new(index, 0)
+    }
+
+    /// アクターを削除（古いハンドルなら何もしない）
+    pub fn remove(&mut self, actor_id: ActorId) -> Option<Actor> {
+        let slot = self.slots.get_mut(actor_id.index() as usize)?;
+        if slot.generation != actor_id.generation() {
+            return None;
+        }
+        let actor = slot.actor.take()?;
+        self.len -= 1;
+
+        // 世代を使い切ったスロットは再利用しない（古いハンドルと衝突させない）
+        if let Some(generation) = slot.generation.checked_add(1) {
+            slot.generation = generation;
+            self.free_indices.push(actor_id.index());
+        }
+        Some(actor)
+    }
+
+    /// ハンドルが生きているアクターを指しているか
+    pub fn contains(&self, actor_id: ActorId) -> bool {
+        self.get(actor_id).is_some()
+    }
+
+    pub fn get(&self, actor_id: ActorId) -> Option<&Actor> {
+        let slot = self.slots.get(actor_id.index() as usize)?;
+        if slot.generation != actor_id.generation() {
+            return None;
+        }
+        slot.actor.as_ref()
+    }
+
+    pub fn get_mut(&mut self, actor_id: ActorId) -> Option<&mut Actor> {
+        let slot = self.slots.get_mut(actor_id.index() as usize)?;
+        if slot.generation != actor_id.generation() {
+            return None;
+        }
+        slot.actor.as_mut()
+    }
+
+    /// 生きているアクターの数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 生きているアクターをハンドルと一緒に列挙
+    pub fn iter(&self) -> impl Iterator<Item = (ActorId, &Actor)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.actor
+                .as_ref()
+                .map(|actor| (ActorId::new(index as u32, slot.generation), actor))
+        })
+    }
+
+    /// 生きているアクターをハンドルと一緒に可変で列挙
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ActorId, &mut Actor)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let generation = slot.generation;
+                slot.actor
+                    .as_mut()
+                    .map(|actor| (ActorId::new(index as u32, generation), actor))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_handle_does_not_resolve_after_slot_reuse() {
+        let mut storage = ActorStorage::new();
+        let old_id = storage.insert(Actor::new());
+        assert!(storage.remove(old_id).is_some());
+
+        // 空いたスロットが世代を進めて再利用される
+        let new_id = storage.insert(Actor::new());
+        assert_eq!(new_id.index(), old_id.index());
+        assert_ne!(new_id.generation(), old_id.generation());
+
+        assert!(storage.get(old_id).is_none());
+        assert!(storage.get_mut(old_id).is_none());
+        assert!(!storage.contains(old_id));
+        assert!(storage.get(new_id).is_some());
+        assert!(storage.get_mut(new_id).is_some());
+
+        // 古いハンドルで削除しても新しいアクターは消えない
+        assert!(storage.remove(old_id).is_none());
+        assert!(storage.contains(new_id));
+        assert_eq!(storage.len(), 1);
+    }
+}
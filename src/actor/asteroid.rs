@@ -1,59 +1,131 @@
-use super::actor::Actor;
-use super::component::{Component, ComponentBase};
-use super::game::Game;
-use super::sprite_component::SpriteComponent;
+use super::actor::ActorStatus;
+use super::collision::CollisionEvent;
+use super::component::{Component, ComponentContext};
+use super::laser::Laser;
+use super::math::Math;
+use super::random::{Random, RandomStream};
+use super::score::ScoreSource;
 use super::vector2::Vector2;
-use std::f32::consts::PI;
 
-pub struct Asteroid {
-    base: ComponentBase,
-    rotation_speed: f32,
+/// 小惑星の大きさの段階
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
 }
 
-impl Asteroid {
-    pub fn new(game: &mut Game) -> Self {
-        let mut asteroid = Self {
-            base: ComponentBase::new(),
-            rotation_speed: rand::random::<f32>() * PI - PI / 2.0,
-        };
+impl AsteroidSize {
+    /// スプライトのスケール
+    pub fn scale(&self) -> f32 {
+        match self {
+            AsteroidSize::Large => 1.0,
+            AsteroidSize::Medium => 0.6,
+            AsteroidSize::Small => 0.35,
+        }
+    }
 
-        // スプライトコンポーネントを追加
-        let sprite = SpriteComponent::new("Asteroid.png", 100, &mut game.texture_manager);
-        asteroid.add_component(Box::new(sprite));
+    /// 衝突判定の半径
+    pub fn radius(&self) -> f32 {
+        40.0 * self.scale()
+    }
 
-        asteroid
+    /// 破壊したときの標準の得点（ScoreConfigで変更できる）
+    pub fn score(&self) -> u32 {
+        match self {
+            AsteroidSize::Large => 20,
+            AsteroidSize::Medium => 50,
+            AsteroidSize::Small => 100,
+        }
     }
 
-    pub fn add_component(&mut self, component: Box<dyn Component>) {
-        if let Some(actor) = self.owner_mut() {
-            actor.add_component(component);
+    /// 分裂したときの破片の大きさ（最小なら分裂しない）
+    pub fn smaller(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
         }
     }
 }
 
-impl Component for Asteroid {
-    fn update(&mut self, delta_time: f32) {
-        let rotation_speed = self.rotation_speed; // 先に値を取得
-        if let Some(actor) = self.owner_mut() {
-            let rotation = actor.rotation();
-            actor.set_rotation(rotation + rotation_speed * delta_time);
-
-            let forward = Vector2::new(rotation.cos(), rotation.sin());
-            let pos = actor.position();
-            actor.set_position(pos + forward * 150.0 * delta_time);
+/// 回転しながら一定の速度で漂う小惑星
+///
+/// スプライトはWorld::create_asteroidで同じアクターに付けられる。
+/// レーザーで壊されると、1段階小さい破片に分裂する。
+pub struct Asteroid {
+    size: AsteroidSize,
+    velocity: Vector2,
+    rotation_speed: f32,
+}
+
+impl Asteroid {
+    /// rotation_speedは見た目の回転の速さ（ラジアン/秒）
+    pub fn new(size: AsteroidSize, velocity: Vector2, rotation_speed: f32) -> Self {
+        Self {
+            size,
+            velocity,
+            rotation_speed,
         }
     }
 
-    fn set_owner(&mut self, owner: &mut Actor) {
-        self.base.set_owner(owner);
+    pub fn size(&self) -> AsteroidSize {
+        self.size
     }
 
-    fn owner(&self) -> Option<&Actor> {
-        self.base.owner()
+    pub fn velocity(&self) -> Vector2 {
+        self.velocity
     }
 
-    fn owner_mut(&mut self) -> Option<&mut Actor> {
-        self.base.owner_mut()
+    /// 破片の速度（元の進行方向から左右に散らし、少し速くする）
+    fn fragment_velocities(&self, count: usize, random: &mut Random) -> Vec<Vector2> {
+        let speed = self.velocity.length().max(50.0) * 1.3;
+        let heading = self.velocity.y.atan2(self.velocity.x);
+        let spread = Math::to_rad(120.0);
+        (0..count)
+            .map(|i| {
+                // -spread/2 から spread/2 の間に均等に並べ、少しばらつかせる
+                let t = i as f32 / (count - 1).max(1) as f32 - 0.5;
+                let angle = heading + spread * t + random.float() * Math::to_rad(10.0);
+                Vector2::new(angle.cos(), angle.sin()) * speed
+            })
+            .collect()
+    }
+
+    /// 小惑星を壊し、得点と破片の生成を予約する
+    fn destroy(&mut self, ctx: &mut ComponentContext) {
+        ctx.owner_mut().set_status(ActorStatus::Dead);
+        ctx.commands().award(ScoreSource::Asteroid(self.size));
+
+        if let Some(smaller) = self.size.smaller() {
+            let pos = ctx.owner().position();
+            let random = ctx.random(RandomStream::Spawning);
+            let count = random.int_range(2, 3) as usize;
+            let velocities = self.fragment_velocities(count, random);
+            ctx.commands().spawn(move |world| {
+                for velocity in velocities {
+                    world.create_asteroid(pos, velocity, smaller);
+                }
+            });
+        }
+    }
+}
+
+impl Component for Asteroid {
+    fn update(&mut self, ctx: &mut ComponentContext, delta_time: f32) {
+        let actor = ctx.owner_mut();
+        let rotation = actor.rotation();
+        actor.set_rotation(rotation + self.rotation_speed * delta_time);
+
+        let pos = actor.position();
+        actor.set_position(pos + self.velocity * delta_time);
+    }
+
+    fn on_collision(&mut self, ctx: &mut ComponentContext, event: &CollisionEvent) {
+        // レーザーに撃たれたら壊れる
+        if event.other_has::<Laser>() {
+            self.destroy(ctx);
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -0,0 +1,298 @@
+use super::sprite_instance::SpriteInstance;
+use super::texture::{Texture, TextureManager};
+use super::vector2::Vector2;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// フォントにない文字の代わりに描く文字
+const FALLBACK_CHAR: char = '?';
+
+/// 文字列を揃える位置
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextAlign {
+    /// 指定した位置が各行の左端
+    Left,
+    /// 指定した位置が各行の中央
+    Center,
+    /// 指定した位置が各行の右端
+    Right,
+}
+
+/// フォント定義ファイルの1ページ分（グリフを並べた画像1枚）
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontPage {
+    pub texture_name: String,
+    pub cell_width: u32,
+    pub cell_height: u32,
+    /// セルに左上から行ごとに並んでいる文字
+    pub chars: Vec<char>,
+}
+
+/// グリフ1文字分
+#[derive(Debug, Copy, Clone)]
+struct Glyph {
+    page: usize,
+    uv_offset: [f32; 2],
+    uv_size: [f32; 2],
+    /// ピクセル単位の大きさ（送り幅を兼ねる）
+    size: Vector2,
+}
+
+/// グリフを画像に並べたビットマップフォント
+///
+/// グリフは文字（char）で引くので、画像を追加すれば日本語などの全角文字も描ける。
+/// 全角文字は大きいセルのページに置けば、そのセルの幅だけ送られる。
+pub struct BitmapFont {
+    pages: Vec<Arc<Texture>>,
+    glyphs: HashMap<char, Glyph>,
+    line_height: f32,
+}
+
+impl BitmapFont {
+    /// assetsフォルダのフォント定義ファイルを読み込み、画像をTextureManagerに読み込む
+    pub fn load(definition_name: &str, texture_manager: &mut TextureManager) -> Option<Self> {
+        let path = Path::new("assets").join(definition_name);
+        println!("Loading font: {:?}", path);
+        let pages = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Self::parse(&text))
+        {
+            Ok(pages) => pages,
+            Err(e) => {
+                println!("Failed to load font: {:?} - Error: {}", path, e);
+                return None;
+            }
+        };
+
+        let mut font = Self {
+            pages: Vec::new(),
+            glyphs: HashMap::new(),
+            line_height: 0.0,
+        };
+        for page in &pages {
+            texture_manager.load_texture(&page.texture_name);
+            match texture_manager.get_texture(&page.texture_name) {
+                Some(texture) => font.add_page(page, texture),
+                None => println!("Failed to load font page: {}", page.texture_name),
+            }
+        }
+        if font.glyphs.is_empty() {
+            println!("Font has no glyphs: {:?}", path);
+            return None;
+        }
+        Some(font)
+    }
+
+    /// フォント定義ファイルの内容を解析
+    ///
+    /// `#`で始まる行はコメント。`page <画像> <セルの幅> <セルの高さ>`で画像を指定し、
+    /// 続く`chars <文字列>`の文字をその画像のセルに順に割り当てる。
+    pub fn parse(text: &str) -> Result<Vec<FontPage>, String> {
+        let mut pages: Vec<FontPage> = Vec::new();
+        for (line_number, line) in text.lines().enumerate() {
+            if let Some(chars) = line.strip_prefix("chars ") {
+                let page = pages
+                    .last_mut()
+                    .ok_or_else(|| format!("line {}: `chars` before `page`", line_number + 1))?;
+                page.chars.extend(chars.chars());
+                continue;
+            }
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                ["page", texture_name, width, height] => {
+                    let size = |value: &str| {
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|&size| size > 0)
+                            .ok_or_else(|| {
+                                format!("line {}: bad cell size `{}`", line_number + 1, value)
+                            })
+                    };
+                    pages.push(FontPage {
+                        texture_name: texture_name.to_string(),
+                        cell_width: size(width)?,
+                        cell_height: size(height)?,
+                        chars: Vec::new(),
+                    });
+                }
+                _ => return Err(format!("line {}: unknown line `{}`", line_number + 1, line)),
+            }
+        }
+        Ok(pages)
+    }
+
+    /// ページの文字をセルに割り当てる（先に登録された文字が優先）
+    fn add_page(&mut self, page: &FontPage, texture: Arc<Texture>) {
+        self.add_glyphs(page, self.pages.len(), texture.size);
+        self.pages.push(texture);
+    }
+
+    /// 画像の大きさからページの文字のセルの位置を求めて登録
+    fn add_glyphs(&mut self, page: &FontPage, page_index: usize, (width, height): (u32, u32)) {
+        let columns = (width / page.cell_width).max(1) as usize;
+        let rows = (height / page.cell_height) as usize;
+        for (index, &c) in page.chars.iter().enumerate() {
+            let (column, row) = (index % columns, index / columns);
+            if row >= rows {
+                println!("Font page {} has no cell for: {:?}", page.texture_name, c);
+                break;
+            }
+            self.glyphs.entry(c).or_insert(Glyph {
+                page: page_index,
+                uv_offset: [
+                    (column as u32 * page.cell_width) as f32 / width as f32,
+                    (row as u32 * page.cell_height) as f32 / height as f32,
+                ],
+                uv_size: [
+                    page.cell_width as f32 / width as f32,
+                    page.cell_height as f32 / height as f32,
+                ],
+                size: Vector2::new(page.cell_width as f32, page.cell_height as f32),
+            });
+        }
+        self.line_height = self.line_height.max(page.cell_height as f32);
+    }
+
+    /// 文字のグリフ（なければ代わりの文字のグリフ）
+    fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs
+            .get(&c)
+            .or_else(|| self.glyphs.get(&FALLBACK_CHAR))
+    }
+
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.glyphs.contains_key(&c)
+    }
+
+    /// 1行の高さ（スケール1のピクセル数）
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// 1行の幅（スケール1のピクセル数）
+    fn line_width(&self, line: &str) -> f32 {
+        line.chars()
+            .map(|c| self.glyph(c).map_or(0.0, |glyph| glyph.size.x))
+            .sum()
+    }
+
+    /// 文字列を描いたときの大きさ（`\n`で改行する）
+    pub fn measure(&self, text: &str, scale: f32) -> Vector2 {
+        let width = text
+            .split('\n')
+            .map(|line| self.line_width(line))
+            .fold(0.0, f32::max);
+        let lines = text.split('\n').count();
+        Vector2::new(width, self.line_height * lines as f32) * scale
+    }
+
+    /// 文字列をグリフのインスタンスに並べる
+    ///
+    /// positionは1行目の上端で、左右はalignに従う。空白は描かずに送るだけ。
+    pub fn layout(
+        &self,
+        text: &str,
+        position: Vector2,
+        scale: f32,
+        color: [f32; 4],
+        align: TextAlign,
+    ) -> Vec<(Arc<Texture>, SpriteInstance)> {
+        let mut instances = Vec::new();
+        for (line_index, line) in text.split('\n').enumerate() {
+            let width = self.line_width(line) * scale;
+            let mut x = match align {
+                TextAlign::Left => position.x,
+                TextAlign::Center => position.x - width / 2.0,
+                TextAlign::Right => position.x - width,
+            };
+            let top = position.y + self.line_height * scale * line_index as f32;
+
+            for c in line.chars() {
+                let Some(glyph) = self.glyph(c) else {
+                    continue;
+                };
+                let size = glyph.size * scale;
+                if !c.is_whitespace() {
+                    // グリフの下端を行の下端に揃える
+                    let center = Vector2::new(
+                        x + size.x / 2.0,
+                        top + self.line_height * scale - size.y / 2.0,
+                    );
+                    let instance = SpriteInstance::new(
+                        center,
+                        0.0,
+                        Vector2::one() * scale,
+                        (glyph.size.x as u32, glyph.size.y as u32),
+                    )
+                    .with_uv(glyph.uv_offset, glyph.uv_size)
+                    .with_color(color);
+                    instances.push((self.pages[glyph.page].clone(), instance));
+                }
+                x += size.x;
+            }
+        }
+        instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// assetsのフォント定義を、画像の大きさだけ読んで組み立てる（GPUは使わない）
+    fn load_asset_font() -> (BitmapFont, Vec<FontPage>) {
+        let text = fs::read_to_string(Path::new("assets").join("Font.txt")).unwrap();
+        let pages = BitmapFont::parse(&text).unwrap();
+        let mut font = BitmapFont {
+            pages: Vec::new(),
+            glyphs: HashMap::new(),
+            line_height: 0.0,
+        };
+        for (index, page) in pages.iter().enumerate() {
+            let size = image::image_dimensions(Path::new("assets").join(&page.texture_name));
+            font.add_glyphs(page, index, size.unwrap());
+        }
+        (font, pages)
+    }
+
+    #[test]
+    fn japanese_text_uses_kana_glyphs() {
+        let (font, pages) = load_asset_font();
+        let text = "ゲームオーバー";
+        for c in text.chars() {
+            assert!(font.has_glyph(c), "no glyph for {:?}", c);
+        }
+        // 代わりの`?`（幅12）ではなく全角のセル（幅20）で送られる
+        assert_eq!(font.measure(text, 1.0), Vector2::new(20.0 * 7.0, 20.0));
+
+        // 割り当てられたセルに実際に描かれている
+        let kana_page = pages.iter().position(|page| page.chars.contains(&'ゲ'));
+        let page = &pages[kana_page.unwrap()];
+        let image = image::open(Path::new("assets").join(&page.texture_name))
+            .unwrap()
+            .to_rgba8();
+        for c in text.chars() {
+            let glyph = font.glyphs[&c];
+            let x = (glyph.uv_offset[0] * image.width() as f32).round() as u32;
+            let y = (glyph.uv_offset[1] * image.height() as f32).round() as u32;
+            let drawn = (0..page.cell_height)
+                .flat_map(|dy| (0..page.cell_width).map(move |dx| (x + dx, y + dy)))
+                .any(|(px, py)| image.get_pixel(px, py)[3] > 0);
+            assert!(drawn, "empty cell for {:?}", c);
+        }
+    }
+
+    #[test]
+    fn missing_glyph_falls_back() {
+        let (font, _) = load_asset_font();
+        assert!(!font.has_glyph('漢'));
+        assert_eq!(font.measure("漢", 1.0), font.measure("?", 1.0));
+    }
+}
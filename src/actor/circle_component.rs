@@ -1,17 +1,33 @@
-use super::actor::Actor;
-use super::component::{Component, ComponentBase};
+use super::actor::ActorState;
+use super::collision_layers::LayerMask;
+use super::component::{Component, ComponentContext};
+use super::math::Math;
 use super::vector2::Vector2;
 
 pub struct CircleComponent {
-    base: ComponentBase,
     radius: f32,
+    /// この円が属するレイヤー
+    layer: LayerMask,
+    /// 衝突する相手のレイヤー
+    collides_with: LayerMask,
 }
 
 impl CircleComponent {
+    /// 全てのレイヤーと衝突する円を作成
     pub fn new(radius: f32) -> Self {
         Self {
-            base: ComponentBase::new(),
             radius,
+            layer: LayerMask::ALL,
+            collides_with: LayerMask::ALL,
+        }
+    }
+
+    /// レイヤーと衝突相手のマスクを指定して円を作成
+    pub fn with_layers(radius: f32, layer: LayerMask, collides_with: LayerMask) -> Self {
+        Self {
+            radius,
+            layer,
+            collides_with,
         }
     }
 
@@ -23,35 +39,85 @@ impl CircleComponent {
         self.radius = radius;
     }
 
-    pub fn intersect(&mut self, other: &mut CircleComponent) -> bool {
-        if let (Some(owner), Some(other_owner)) = (self.owner_mut(), other.owner_mut()) {
-            let pos1 = owner.position();
-            let pos2 = other_owner.position();
-            let diff = pos1 - pos2;
-            let dist_sq = diff.length_squared();
-            let radii = self.radius + other.radius;
-            dist_sq <= radii * radii
-        } else {
-            false
-        }
+    pub fn layer(&self) -> LayerMask {
+        self.layer
     }
-}
 
-impl Component for CircleComponent {
-    fn update(&mut self, _delta_time: f32) {}
+    pub fn set_layer(&mut self, layer: LayerMask) {
+        self.layer = layer;
+    }
 
-    fn set_owner(&mut self, owner: &mut Actor) {
-        self.base.set_owner(owner);
+    pub fn collides_with(&self) -> LayerMask {
+        self.collides_with
     }
 
-    fn owner(&self) -> Option<&Actor> {
-        self.base.owner()
+    pub fn set_collides_with(&mut self, collides_with: LayerMask) {
+        self.collides_with = collides_with;
     }
 
-    fn owner_mut(&mut self) -> Option<&mut Actor> {
-        self.base.owner_mut()
+    /// お互いのマスクが相手のレイヤーを含んでいるときだけ衝突判定の対象になる
+    pub fn can_collide(&self, other: &CircleComponent) -> bool {
+        self.collides_with.intersects(other.layer) && other.collides_with.intersects(self.layer)
     }
 
+    /// 円の中心（所有者アクターの位置）
+    pub fn center(&self, owner: &ActorState) -> Vector2 {
+        owner.position()
+    }
+
+    /// この円の中心からotherの円の中心へのベクトル
+    ///
+    /// world_sizeを指定すると画面端で折り返す空間での最短のベクトルになる。
+    pub fn offset_to(
+        &self,
+        owner: &ActorState,
+        other: &CircleComponent,
+        other_owner: &ActorState,
+        world_size: Option<Vector2>,
+    ) -> Vector2 {
+        let diff = other.center(other_owner) - self.center(owner);
+        match world_size {
+            Some(size) => Vector2::new(
+                Math::wrapped_delta(diff.x, size.x),
+                Math::wrapped_delta(diff.y, size.y),
+            ),
+            None => diff,
+        }
+    }
+
+    /// ownerに付いたこの円と、other_ownerに付いたotherの円が重なっているか
+    pub fn intersect(
+        &self,
+        owner: &ActorState,
+        other: &CircleComponent,
+        other_owner: &ActorState,
+    ) -> bool {
+        self.overlaps(self.offset_to(owner, other, other_owner, None), other)
+    }
+
+    /// 画面端で折り返す空間（トーラス）で2つの円が重なっているか
+    pub fn intersect_toroidal(
+        &self,
+        owner: &ActorState,
+        other: &CircleComponent,
+        other_owner: &ActorState,
+        world_size: Vector2,
+    ) -> bool {
+        self.overlaps(
+            self.offset_to(owner, other, other_owner, Some(world_size)),
+            other,
+        )
+    }
+
+    fn overlaps(&self, offset: Vector2, other: &CircleComponent) -> bool {
+        let radii = self.radius + other.radius;
+        offset.length_squared() <= radii * radii
+    }
+}
+
+impl Component for CircleComponent {
+    fn update(&mut self, _ctx: &mut ComponentContext, _delta_time: f32) {}
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -0,0 +1,167 @@
+use super::actor::ActorState;
+use super::actor_id::ActorId;
+use super::circle_component::CircleComponent;
+use super::component::Component;
+use super::math::Math;
+use super::spatial_hash::SpatialHash;
+use super::vector2::Vector2;
+use std::any::TypeId;
+
+/// 衝突判定に参加するアクター1つ分
+pub struct CollisionBody<'a> {
+    pub actor_id: ActorId,
+    pub owner: &'a ActorState,
+    pub circle: &'a CircleComponent,
+}
+
+/// 2つのアクターの衝突
+#[derive(Debug, Copy, Clone)]
+pub struct Collision {
+    pub actor_a: ActorId,
+    pub actor_b: ActorId,
+    /// 接触点（2つの円の中心を半径の比で分けた点）
+    pub point: Vector2,
+}
+
+/// コンポーネントに届けられる衝突イベント
+pub struct CollisionEvent<'a> {
+    other: ActorId,
+    point: Vector2,
+    /// 相手のアクターに付いているコンポーネントの型
+    other_components: &'a [TypeId],
+}
+
+impl<'a> CollisionEvent<'a> {
+    pub fn new(other: ActorId, point: Vector2, other_components: &'a [TypeId]) -> Self {
+        Self {
+            other,
+            point,
+            other_components,
+        }
+    }
+
+    /// 衝突した相手のアクター
+    pub fn other(&self) -> ActorId {
+        self.other
+    }
+
+    /// 接触点
+    pub fn point(&self) -> Vector2 {
+        self.point
+    }
+
+    /// 相手のアクターが指定した型のコンポーネントを持っているか
+    pub fn other_has<T: Component>(&self) -> bool {
+        self.other_components.contains(&TypeId::of::<T>())
+    }
+}
+
+/// CircleComponentを持つアクター同士の衝突を検出する
+///
+/// 空間ハッシュで近くにある組だけを候補に絞ってから円同士の判定を行う。
+/// 画面サイズを設定すると、画面端で折り返す空間として距離を測る。
+pub struct CollisionSystem {
+    spatial_hash: SpatialHash,
+    /// 画面端で折り返す場合の画面サイズ
+    world_size: Option<Vector2>,
+}
+
+impl Default for CollisionSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CollisionSystem {
+    pub fn new() -> Self {
+        Self {
+            spatial_hash: SpatialHash::new(1.0),
+            world_size: None,
+        }
+    }
+
+    /// 画面端で折り返す空間の大きさを設定（Noneなら折り返さない）
+    pub fn set_world_size(&mut self, world_size: Option<Vector2>) {
+        self.world_size = world_size;
+    }
+
+    /// 重なっているペアを返す（結果と順番はfind_collisions_brute_forceと同じ）
+    pub fn find_collisions(&mut self, bodies: &[CollisionBody]) -> Vec<Collision> {
+        // セルの大きさは最大の円の直径にする
+        let max_radius = bodies
+            .iter()
+            .map(|body| body.circle.radius())
+            .fold(0.0, f32::max);
+        match self.world_size {
+            Some(world_size) => self
+                .spatial_hash
+                .reset_wrapped(max_radius * 2.0, world_size),
+            None => self.spatial_hash.reset(max_radius * 2.0),
+        }
+        for (index, body) in bodies.iter().enumerate() {
+            self.spatial_hash
+                .insert(index, body.circle.center(body.owner), body.circle.radius());
+        }
+
+        self.spatial_hash
+            .candidate_pairs()
+            .into_iter()
+            .filter_map(|(i, j)| {
+                let (a, b) = (&bodies[i], &bodies[j]);
+                self.test(a, b).then(|| self.collision(a, b))
+            })
+            .collect()
+    }
+
+    /// 全ての組み合わせを調べて重なっているペアを返す
+    pub fn find_collisions_brute_force(&self, bodies: &[CollisionBody]) -> Vec<Collision> {
+        let mut collisions = Vec::new();
+        for (i, a) in bodies.iter().enumerate() {
+            for b in &bodies[i + 1..] {
+                if self.test(a, b) {
+                    collisions.push(self.collision(a, b));
+                }
+            }
+        }
+        collisions
+    }
+
+    /// レイヤーが衝突対象で、かつ円が重なっているか
+    fn test(&self, a: &CollisionBody, b: &CollisionBody) -> bool {
+        if !a.circle.can_collide(b.circle) {
+            return false;
+        }
+        match self.world_size {
+            Some(world_size) => a
+                .circle
+                .intersect_toroidal(a.owner, b.circle, b.owner, world_size),
+            None => a.circle.intersect(a.owner, b.circle, b.owner),
+        }
+    }
+
+    fn collision(&self, a: &CollisionBody, b: &CollisionBody) -> Collision {
+        let center_a = a.circle.center(a.owner);
+        let offset = a
+            .circle
+            .offset_to(a.owner, b.circle, b.owner, self.world_size);
+        let radii = a.circle.radius() + b.circle.radius();
+        let t = if radii > 0.0 {
+            a.circle.radius() / radii
+        } else {
+            0.5
+        };
+        Collision {
+            actor_a: a.actor_id,
+            actor_b: b.actor_id,
+            point: self.wrap_point(center_a + offset * t),
+        }
+    }
+
+    /// 折り返す空間なら接触点を画面内に収める
+    fn wrap_point(&self, point: Vector2) -> Vector2 {
+        match self.world_size {
+            Some(size) => Vector2::new(Math::wrap(point.x, size.x), Math::wrap(point.y, size.y)),
+            None => point,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// 衝突レイヤーのビットマスク
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LayerMask(u32);
+
+impl LayerMask {
+    /// どのレイヤーも含まない
+    pub const NONE: LayerMask = LayerMask(0);
+    /// 全てのレイヤーを含む
+    pub const ALL: LayerMask = LayerMask(u32::MAX);
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// 2つのマスクに共通のレイヤーがあるか
+    pub fn intersects(&self, other: LayerMask) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for LayerMask {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        LayerMask(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for LayerMask {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// 衝突レイヤーの名前とビットの対応表
+///
+/// レイヤーは登録順に1ビットずつ割り当てられる（最大32個）。
+pub struct CollisionLayers {
+    names: Vec<String>,
+}
+
+impl Default for CollisionLayers {
+    /// Asteroidsで使う標準のレイヤー
+    fn default() -> Self {
+        Self::new(&["ship", "asteroid", "player_laser", "enemy_laser", "pickup"])
+    }
+}
+
+impl CollisionLayers {
+    /// 指定した名前のレイヤーを順に登録した対応表を作成
+    pub fn new(names: &[&str]) -> Self {
+        let mut layers = Self { names: Vec::new() };
+        for name in names {
+            layers.register(name);
+        }
+        layers
+    }
+
+    /// レイヤーを登録してそのマスクを返す（登録済みならそのマスクを返す）
+    pub fn register(&mut self, name: &str) -> Option<LayerMask> {
+        if let Some(layer) = self.get(name) {
+            return Some(layer);
+        }
+        if self.names.len() >= u32::BITS as usize {
+            println!("Too many collision layers, cannot register: {}", name);
+            return None;
+        }
+        self.names.push(name.to_string());
+        Some(LayerMask(1 << (self.names.len() - 1)))
+    }
+
+    /// 名前からレイヤーのマスクを取得
+    pub fn get(&self, name: &str) -> Option<LayerMask> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|index| LayerMask(1 << index))
+    }
+
+    /// 複数のレイヤーをまとめたマスクを取得（未登録の名前があればエラー）
+    pub fn mask(&self, names: &[&str]) -> Result<LayerMask, String> {
+        let mut mask = LayerMask::NONE;
+        for name in names {
+            match self.get(name) {
+                Some(layer) => mask |= layer,
+                None => return Err(format!("unknown collision layer: {}", name)),
+            }
+        }
+        Ok(mask)
+    }
+
+    /// 登録済みのレイヤー名
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
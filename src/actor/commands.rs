@@ -0,0 +1,60 @@
+use super::actor_id::ActorId;
+use super::score::ScoreSource;
+use super::world::World;
+
+/// 更新中に積まれ、フレームの決まったタイミングでWorldに適用される命令
+pub enum Command {
+    /// アクターを生成する処理
+    Spawn(Box<dyn FnOnce(&mut World)>),
+    /// 指定したアクターを削除
+    Despawn(ActorId),
+    /// 得点を加える
+    Award(ScoreSource),
+}
+
+/// コンポーネントの更新中にアクターの生成・削除や得点を予約するためのバッファ
+///
+/// アクターの更新中はWorldを借用できないため、生成・削除はここに積んでおき、
+/// 全アクターの更新が終わった後にWorld::apply_commandsでまとめて適用する。
+pub struct Commands {
+    queue: Vec<Command>,
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self { queue: Vec::new() }
+    }
+
+    /// アクターの生成を予約
+    pub fn spawn<F>(&mut self, spawn: F)
+    where
+        F: FnOnce(&mut World) + 'static,
+    {
+        self.queue.push(Command::Spawn(Box::new(spawn)));
+    }
+
+    /// アクターの削除を予約
+    pub fn despawn(&mut self, actor_id: ActorId) {
+        self.queue.push(Command::Despawn(actor_id));
+    }
+
+    /// 得点の加算を予約
+    pub fn award(&mut self, source: ScoreSource) {
+        self.queue.push(Command::Award(source));
+    }
+
+    /// 積まれた命令を全て取り出す
+    pub fn take(&mut self) -> Vec<Command> {
+        std::mem::take(&mut self.queue)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
@@ -1,47 +1,106 @@
-use super::actor::Actor;
+use super::actor::ActorState;
+use super::actor_id::ActorId;
+use super::collision::CollisionEvent;
+use super::commands::Commands;
+use super::input_map::Action;
+use super::random::{Random, RandomStream, RandomStreams};
 use std::any::Any;
 
 /// コンポーネントの基本トレイト
 pub trait Component: Any {
     /// コンポーネントの更新処理
-    fn update(&mut self, delta_time: f32);
+    fn update(&mut self, ctx: &mut ComponentContext, delta_time: f32);
 
-    /// コンポーネントの初期化処理
-    fn start(&mut self) {}
+    /// 入力の処理（押されているキーはInputMapで操作に変換されて渡される）
+    fn process_input(&mut self, _ctx: &mut ComponentContext, _actions: &[Action]) {}
 
-    /// 所有者のアクターを設定
-    fn set_owner(&mut self, owner: &mut Actor);
+    /// 所有者のアクターが他のアクターと衝突したときの処理
+    fn on_collision(&mut self, _ctx: &mut ComponentContext, _event: &CollisionEvent) {}
 
-    /// 所有者のアクターを取得
-    fn owner(&self) -> Option<&Actor>;
-
-    /// 所有者のアクターを可変参照で取得
-    fn owner_mut(&mut self) -> Option<&mut Actor>;
+    /// コンポーネントの初期化処理
+    fn start(&mut self) {}
 
     fn as_any_mut(&mut self) -> &mut dyn Any;
 
     fn as_any(&self) -> &dyn Any;
 }
 
-/// コンポーネントの基本実装のためのベース構造体
-pub struct ComponentBase {
-    owner: Option<*mut Actor>,
+/// 更新中のコンポーネントに渡される、所有者アクターとゲームへのアクセス
+///
+/// 所有者の状態と、同じアクターに付いている他のコンポーネントを借用する。
+/// 更新中のコンポーネント自身は含まれない。
+/// 他のアクターの生成・削除はcommandsに予約する。
+/// 乱数はthread_rngではなくrandomから取り、シードから再現できるようにする。
+pub struct ComponentContext<'a> {
+    /// 所有者のアクターID
+    actor_id: ActorId,
+    owner: &'a mut ActorState,
+    /// 更新中のコンポーネントより前にあるコンポーネント
+    before: &'a mut [Box<dyn Component>],
+    /// 更新中のコンポーネントより後にあるコンポーネント
+    after: &'a mut [Box<dyn Component>],
+    commands: &'a mut Commands,
+    /// ワールドが持つランダム値生成
+    random: &'a mut RandomStreams,
 }
 
-impl ComponentBase {
-    pub fn new() -> Self {
-        Self { owner: None }
+impl<'a> ComponentContext<'a> {
+    pub fn new(
+        actor_id: ActorId,
+        owner: &'a mut ActorState,
+        before: &'a mut [Box<dyn Component>],
+        after: &'a mut [Box<dyn Component>],
+        commands: &'a mut Commands,
+        random: &'a mut RandomStreams,
+    ) -> Self {
+        Self {
+            actor_id,
+            owner,
+            before,
+            after,
+            commands,
+            random,
+        }
+    }
+
+    /// 所有者のアクターID
+    pub fn actor_id(&self) -> ActorId {
+        self.actor_id
+    }
+
+    /// アクターの生成・削除を予約するバッファ
+    pub fn commands(&mut self) -> &mut Commands {
+        self.commands
+    }
+
+    /// 指定した用途のランダム値生成（同じシードなら同じ列になる）
+    pub fn random(&mut self, stream: RandomStream) -> &mut Random {
+        self.random.get(stream)
+    }
+
+    /// 所有者のアクターの状態を取得
+    pub fn owner(&self) -> &ActorState {
+        self.owner
     }
 
-    pub fn set_owner(&mut self, owner: &mut Actor) {
-        self.owner = Some(owner as *mut Actor);
+    /// 所有者のアクターの状態を可変参照で取得
+    pub fn owner_mut(&mut self) -> &mut ActorState {
+        self.owner
     }
 
-    pub fn owner(&self) -> Option<&Actor> {
-        unsafe { self.owner.map(|ptr| &*ptr) }
+    /// 同じアクターに付いている他のコンポーネントを取得
+    pub fn get_component<T: 'static>(&self) -> Option<&T> {
+        self.before
+            .iter()
+            .chain(self.after.iter())
+            .find_map(|component| component.as_ref().as_any().downcast_ref::<T>())
     }
 
-    pub fn owner_mut(&mut self) -> Option<&mut Actor> {
-        unsafe { self.owner.map(|ptr| &mut *ptr) }
+    /// 同じアクターに付いている他のコンポーネントを可変参照で取得
+    pub fn get_component_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.before
+            .iter_mut()
+            .chain(self.after.iter_mut())
+            .find_map(|component| component.as_mut().as_any_mut().downcast_mut::<T>())
     }
 }
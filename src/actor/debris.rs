@@ -0,0 +1,42 @@
+use super::actor::ActorStatus;
+use super::component::{Component, ComponentContext};
+use super::vector2::Vector2;
+
+/// 爆発で飛び散り、一定時間で消える破片
+///
+/// スプライトはWorld::create_explosionで同じアクターに付けられる。
+pub struct Debris {
+    velocity: Vector2,
+    life_timer: f32,
+}
+
+impl Debris {
+    pub fn new(velocity: Vector2, lifetime: f32) -> Self {
+        Self {
+            velocity,
+            life_timer: lifetime,
+        }
+    }
+}
+
+impl Component for Debris {
+    fn update(&mut self, ctx: &mut ComponentContext, delta_time: f32) {
+        self.life_timer -= delta_time;
+
+        let actor = ctx.owner_mut();
+        let pos = actor.position();
+        actor.set_position(pos + self.velocity * delta_time);
+
+        if self.life_timer <= 0.0 {
+            actor.set_status(ActorStatus::Dead);
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,91 @@
+/// 標準の1秒あたりの更新回数
+const DEFAULT_TICK_RATE: f32 = 60.0;
+
+/// 固定の時間刻みでシミュレーションを進めるための時間の蓄積
+///
+/// フレームの経過時間を貯めておき、刻み幅ぶん貯まるごとに1回更新する。
+/// 余った時間は描画時の補間の割合として使う。
+pub struct FixedTimestep {
+    /// 1回の更新で進める時間（秒）
+    step_time: f32,
+    /// 1フレームで追いつくために行う更新の最大回数
+    max_steps: u32,
+    /// まだ更新に使っていない時間
+    accumulator: f32,
+}
+
+impl Default for FixedTimestep {
+    /// 60Hzで、1フレームに最大5回まで追いつく
+    fn default() -> Self {
+        Self::new(DEFAULT_TICK_RATE, 5)
+    }
+}
+
+impl FixedTimestep {
+    /// tick_rateが正の有限の値でなければ60Hzにする
+    pub fn new(tick_rate: f32, max_steps: u32) -> Self {
+        let mut timestep = Self {
+            step_time: 1.0 / DEFAULT_TICK_RATE,
+            max_steps: max_steps.max(1),
+            accumulator: 0.0,
+        };
+        timestep.set_tick_rate(tick_rate);
+        timestep
+    }
+
+    /// 1秒あたりの更新回数
+    pub fn tick_rate(&self) -> f32 {
+        1.0 / self.step_time
+    }
+
+    /// 正の有限の値でなければ何もしない
+    pub fn set_tick_rate(&mut self, tick_rate: f32) {
+        if tick_rate.is_finite() && tick_rate > 0.0 {
+            self.set_step_time(1.0 / tick_rate);
+        }
+    }
+
+    /// 1回の更新で進める時間（秒）
+    pub fn step_time(&self) -> f32 {
+        self.step_time
+    }
+
+    /// 正の有限の値でなければ何もしない（0では補間の割合が求められない）
+    pub fn set_step_time(&mut self, step_time: f32) {
+        if step_time.is_finite() && step_time > 0.0 {
+            self.step_time = step_time;
+        }
+    }
+
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps.max(1);
+    }
+
+    /// フレームの経過時間を加え、今回行う更新の回数を返す
+    ///
+    /// 最大回数を超える分の遅れ（ウィンドウのドラッグなど）は捨てて、
+    /// 物体が一度に大きく飛ばないようにする。負や有限でない経過時間は0として扱う。
+    pub fn advance(&mut self, delta_time: f32) -> u32 {
+        if delta_time.is_finite() {
+            self.accumulator += delta_time.max(0.0);
+        }
+        let mut steps = 0;
+        while self.accumulator >= self.step_time && steps < self.max_steps {
+            self.accumulator -= self.step_time;
+            steps += 1;
+        }
+        if self.accumulator >= self.step_time {
+            self.accumulator %= self.step_time;
+        }
+        steps
+    }
+
+    /// 前回の更新から次の更新までの補間の割合（0.0〜1.0）
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step_time).clamp(0.0, 1.0)
+    }
+}
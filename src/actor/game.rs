@@ -1,208 +1,396 @@
-use super::actor::Actor;
-use super::input_component::InputComponent;
-use super::laser::Laser;
-use super::move_component::MoveComponent;
-use super::ship::Ship;
-use super::sprite_component::SpriteComponent;
-use super::texture::TextureManager;
+use super::fixed_timestep::FixedTimestep;
+use super::high_score::HighScoreTable;
+use super::input_map::InputMap;
+use super::renderer::Renderer;
+use super::replay::Replay;
+use super::scene::{SceneContext, SceneInput, SceneStack};
+use super::stable_hasher::StableHasher;
+use super::title_scene::TitleScene;
 use super::vector2::Vector2;
-use std::collections::HashMap;
-use std::f32::consts::PI;
-use std::sync::Arc;
-use wgpu;
+use super::wave::WaveConfig;
+use super::world::World;
+use std::hash::Hasher;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 use winit::event::VirtualKeyCode;
-use winit::window::Window;
+
+/// 再生を終えたときのチェックサム
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PlaybackResult {
+    /// 再生を終えたときの状態のチェックサム
+    pub checksum: u64,
+    /// 記録を終えたときのチェックサム（記録に無ければNone）
+    pub recorded_checksum: Option<u64>,
+}
+
+impl PlaybackResult {
+    /// 記録と同じ状態になったか（比べるチェックサムが無ければNone）
+    pub fn matches(&self) -> Option<bool> {
+        self.recorded_checksum
+            .map(|recorded| recorded == self.checksum)
+    }
+}
 
 /// ゲームの状態を管理する構造体
+///
+/// ゲームの中身はシーンのスタックに任せ、入力と時間の進め方を管理する。
+/// 描画はウィンドウがあるときだけRendererが行う。
 pub struct Game {
-    /// アクティブなアクターのマップ
-    actors: HashMap<u32, Actor>,
-    /// 次のアクターID
-    next_actor_id: u32,
+    /// タイトル・プレイ中・一時停止などの画面
+    scenes: SceneStack,
+    /// シーンから使えるハイスコア表などの状態
+    scene_context: SceneContext,
+    /// ウィンドウへの描画（ヘッドレスならNone）
+    renderer: Option<Renderer>,
     /// ゲームが実行中かどうか
     running: bool,
     /// 前回のフレームからの経過時間
     delta_time: f32,
-    pub texture_manager: TextureManager,
-    device: Arc<wgpu::Device>,
-    queue: Arc<wgpu::Queue>,
-    surface: wgpu::Surface,
-    surface_config: wgpu::SurfaceConfiguration,
-    render_pipeline: wgpu::RenderPipeline,
+    /// シーンを固定の時間刻みで進めるための時間の蓄積
+    timestep: FixedTimestep,
     pressed_keys: Vec<VirtualKeyCode>,
+    /// 前回の更新の後に押し始めたキー
+    just_pressed_keys: Vec<VirtualKeyCode>,
+    /// キーと操作の対応表
+    input_map: InputMap,
+    /// 記録中の入力（記録していなければNone）
+    recording: Option<Replay>,
+    /// 再生中の入力（再生していなければNone）
+    playback: Option<Replay>,
+    /// 次に再生する更新の位置
+    playback_tick: usize,
+    /// 終えた再生の結果（まだ取り出されていなければSome）
+    playback_result: Option<PlaybackResult>,
 }
 
 impl Game {
-    /// 新しいゲームインスタンスを作成
+    /// ウィンドウに描画する新しいゲームインスタンスを作成
     pub fn new(window: &winit::window::Window) -> Self {
-        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
-        let surface = unsafe { instance.create_surface(window) }.unwrap();
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            force_fallback_adapter: false,
-            compatible_surface: Some(&surface),
-        }))
-        .unwrap();
-
-        let (device, queue) =
-            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
-                .unwrap();
-
-        let device = Arc::new(device);
-        let queue = Arc::new(queue);
-        let mut texture_manager = TextureManager::new(device.clone(), queue.clone());
-
-        let surface_caps = surface.get_capabilities(&adapter);
-        let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: surface_caps.formats[0],
-            width: window.inner_size().width,
-            height: window.inner_size().height,
-            present_mode: wgpu::PresentMode::Fifo,
-            alpha_mode: surface_caps.alpha_modes[0],
-            view_formats: vec![],
-        };
-        surface.configure(&device, &surface_config);
+        let renderer = Renderer::new(window);
+        let high_score_path = HighScoreTable::default_path();
+        let high_scores = high_score_path
+            .as_ref()
+            .map_or_else(HighScoreTable::new, HighScoreTable::load);
+        let scene_context = SceneContext::new(
+            renderer.screen_size(),
+            WaveConfig::load(Path::new("assets").join("waves.txt")),
+            high_scores,
+            high_score_path,
+            Self::time_seed(),
+        );
+        Self::with_context(scene_context, Some(renderer))
+    }
 
-        // シェーダーとパイプラインの設定
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Sprite Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
-        });
+    /// ウィンドウもGPUも使わないゲームインスタンスを作成
+    ///
+    /// テストやシミュレーション用。ハイスコアはファイルに保存しない。
+    /// 同じシードと同じ入力を与えれば、毎回同じように進む。
+    pub fn headless(world_size: Vector2, wave_config: WaveConfig, seed: u64) -> Self {
+        let scene_context =
+            SceneContext::new(world_size, wave_config, HighScoreTable::new(), None, seed);
+        Self::with_context(scene_context, None)
+    }
 
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(
-                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: Some("Pipeline Layout"),
-                    bind_group_layouts: &[texture_manager.get_bind_group_layout()],
-                    push_constant_ranges: &[],
-                }),
-            ),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                buffers: &[],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+    /// 記録した入力を再生して、ウィンドウに描画するゲームインスタンスを作成
+    ///
+    /// シード・画面の大きさ・ハイスコア表は記録したときのものを使い、ハイスコアは保存しない。
+    pub fn playback(window: &winit::window::Window, replay: Replay) -> Self {
+        let renderer = Renderer::new(window);
+        let scene_context = SceneContext::new(
+            replay.world_size(),
+            WaveConfig::load(Path::new("assets").join("waves.txt")),
+            replay.high_scores().clone(),
+            None,
+            replay.seed(),
+        );
+        let mut game = Self::with_context(scene_context, Some(renderer));
+        game.start_playback(replay);
+        game
+    }
 
-        let mut game = Self {
-            actors: HashMap::new(),
-            next_actor_id: 1,
+    /// 記録した入力を再生する、ウィンドウもGPUも使わないゲームインスタンスを作成
+    ///
+    /// 回帰テスト用。play_to_endで最後まで進めて、チェックサムを記録と比べる。
+    pub fn headless_playback(replay: Replay, wave_config: WaveConfig) -> Self {
+        let scene_context = SceneContext::new(
+            replay.world_size(),
+            wave_config,
+            replay.high_scores().clone(),
+            None,
+            replay.seed(),
+        );
+        let mut game = Self::with_context(scene_context, None);
+        game.start_playback(replay);
+        game
+    }
+
+    fn with_context(mut scene_context: SceneContext, renderer: Option<Renderer>) -> Self {
+        let mut scenes = SceneStack::new();
+        scenes.push(Box::new(TitleScene::new(&mut scene_context)));
+        Self {
+            scenes,
+            scene_context,
+            renderer,
             running: true,
             delta_time: 0.0,
-            device,
-            queue,
-            surface,
-            surface_config,
-            render_pipeline,
-            texture_manager,
+            timestep: FixedTimestep::default(),
             pressed_keys: Vec::new(),
-        };
-
-        // テクスチャを事前にロード
-        println!("Current directory: {:?}", std::env::current_dir().unwrap());
-        game.texture_manager.load_texture("Ship.png");
-        game.texture_manager.load_texture("Asteroid.png");
-        game.texture_manager.load_texture("Laser.png");
-
-        game
+            just_pressed_keys: Vec::new(),
+            input_map: InputMap::default(),
+            recording: None,
+            playback: None,
+            playback_tick: 0,
+            playback_result: None,
+        }
     }
 
     /// 1フレーム分のゲーム更新を実行
+    ///
+    /// フレームの経過時間に応じて、シーンを固定の時間刻みで0回以上進めてから描画する。
     pub fn run(&mut self) {
-        self.process_input();
-        self.update_game();
-        self.generate_output();
+        let steps = self.timestep.advance(self.delta_time);
+        let step_time = self.timestep.step_time();
+        for _ in 0..steps {
+            self.tick(step_time);
+            if !self.running {
+                return;
+            }
+        }
+        if let Some(renderer) = &mut self.renderer {
+            renderer.render(&mut self.scenes, self.timestep.alpha());
+        }
+    }
+
+    /// 押されているキーを指定して、シーンを1回だけ進める（描画はしない）
+    ///
+    /// 前回の呼び出しで押されていなかったキーは押し始めとして扱う。
+    pub fn step(&mut self, pressed_keys: &[VirtualKeyCode], delta_time: f32) {
+        for &key in pressed_keys {
+            if !self.pressed_keys.contains(&key) && !self.just_pressed_keys.contains(&key) {
+                self.just_pressed_keys.push(key);
+            }
+        }
+        self.pressed_keys = pressed_keys.to_vec();
+        self.tick(delta_time);
     }
 
-    /// 新しいアクターを追加
-    pub fn add_actor(&mut self) -> u32 {
-        let actor_id = self.next_actor_id;
-        self.actors.insert(actor_id, Actor::new());
-        self.next_actor_id += 1;
-        actor_id
+    /// 今の入力でシーンを1回進める
+    ///
+    /// 再生中は記録した入力に置き換え、記録中は使った入力を記録する。
+    fn tick(&mut self, delta_time: f32) {
+        if let Some(replay) = &self.playback {
+            if let Some(tick) = replay.ticks().get(self.playback_tick) {
+                self.pressed_keys = tick.held.clone();
+                self.just_pressed_keys = tick.pressed.clone();
+                self.playback_tick += 1;
+            }
+        }
+        if self
+            .recording
+            .as_ref()
+            .is_some_and(|replay| replay.is_full() && replay.checksum().is_none())
+        {
+            // 上限に達したら、そこまでの状態のチェックサムを付けて記録をやめる
+            let checksum = self.checksum();
+            if let Some(replay) = &mut self.recording {
+                replay.set_checksum(checksum);
+            }
+        }
+        if let Some(replay) = &mut self.recording {
+            if replay.checksum().is_none() {
+                replay.push_tick(&self.pressed_keys, &self.just_pressed_keys);
+            }
+        }
+
+        let input = self.scene_input();
+        self.scenes
+            .update(&mut self.scene_context, &input, delta_time);
+        if self.scenes.is_quit() {
+            self.shutdown();
+        }
+
+        if self
+            .playback
+            .as_ref()
+            .is_some_and(|replay| self.playback_tick >= replay.ticks().len())
+        {
+            self.finish_playback();
+        }
     }
 
-    /// アクターを削除
-    pub fn remove_actor(&mut self, actor_id: u32) {
-        self.actors.remove(&actor_id);
+    /// 押されているキーから今回の更新の入力を作る
+    ///
+    /// 押し始めの入力は、次の更新までに離されたキーの分も含めて1回だけ渡す。
+    fn scene_input(&mut self) -> SceneInput {
+        let pressed_keys = std::mem::take(&mut self.just_pressed_keys);
+        SceneInput {
+            actions: self.input_map.actions(&self.pressed_keys),
+            pressed_actions: self.input_map.actions(&pressed_keys),
+            pressed_keys,
+        }
     }
 
-    /// 全てのアクターを更新
-    fn update_game(&mut self) {
-        // アクティブな全てのアクターを更新
-        for actor in self.actors.values_mut() {
-            actor.update(self.delta_time);
+    /// 起動した時刻から決めるシード
+    fn time_seed() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    }
+
+    /// ゲーム全体のシード
+    pub fn seed(&self) -> u64 {
+        self.scene_context.seed()
+    }
+
+    /// ゲームの進行に関わる設定（画面の大きさ・更新の間隔・ウェーブ・キー割り当て）のハッシュ
+    pub fn config_hash(&self) -> u64 {
+        Replay::config_hash(
+            self.scene_context.world_size(),
+            self.timestep.step_time(),
+            self.scene_context.wave_config(),
+            &self.input_map,
+        )
+    }
+
+    /// 全てのシーンの状態とハイスコア表のチェックサム
+    ///
+    /// ハイスコアの日付は実行した日で変わるので含めない。
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(self.scenes.checksum());
+        for entry in self.high_scores().entries() {
+            hasher.write_str(entry.initials());
+            hasher.write_u32(entry.score());
+            hasher.write_u32(entry.wave());
         }
+        hasher.finish()
     }
 
-    /// 入力処理
-    fn process_input(&mut self) {
-        // println!("Processing input with keys: {:?}", self.pressed_keys);
-        for actor in self.actors.values_mut() {
-            if let Some(input) = actor.get_component_mut::<InputComponent>() {
-                input.process_input(&self.pressed_keys);
-            }
+    /// 入力の記録を開始
+    ///
+    /// 作成した直後の状態から再生できるように、最初の更新の前に呼ぶこと。
+    /// 更新の間隔やキー割り当ても、変えるならこれより前に変えておく。
+    /// stepで進める場合は、更新の間隔と同じ時間を渡すこと（再生は更新の間隔で進める）。
+    pub fn start_recording(&mut self) -> Result<(), String> {
+        if self.playback.is_some() {
+            return Err("cannot record while playing back a replay".to_string());
         }
+        self.recording = Some(Replay::new(
+            self.seed(),
+            self.config_hash(),
+            self.scene_context.world_size(),
+            self.timestep.step_time(),
+            self.scene_context.high_scores().clone(),
+        ));
+        Ok(())
     }
 
-    /// 出力生成
-    fn generate_output(&mut self) {
-        let output = self.surface.get_current_texture().unwrap();
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
+    /// 入力の記録を終了し、今の状態のチェックサムを付けて返す
+    ///
+    /// 上限に達して記録をやめていた場合は、そのときのチェックサムのまま返す。
+    pub fn stop_recording(&mut self) -> Option<Replay> {
+        let checksum = self.checksum();
+        self.recording.take().map(|mut replay| {
+            replay.set_checksum(replay.checksum().unwrap_or(checksum));
+            replay
+        })
+    }
 
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// 記録した入力の再生を開始
+    ///
+    /// 設定が記録したときと違うかは、config_hashを記録と比べればわかる。
+    fn start_playback(&mut self, replay: Replay) {
+        self.timestep.set_step_time(replay.step_time());
+        self.playback = Some(replay);
+        self.playback_tick = 0;
+        if self
+            .playback
+            .as_ref()
+            .is_some_and(|replay| replay.ticks().is_empty())
         {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.2,
-                            b: 0.3,
-                            a: 1.0,
-                        }),
-                        store: true,
-                    },
-                })],
-                depth_stencil_attachment: None,
-            });
-
-            render_pass.set_pipeline(&self.render_pipeline);
-
-            // アクターのスプライトを描画
-            for actor in self.actors.values() {
-                if let Some(sprite) = actor.get_component::<SpriteComponent>() {
-                    sprite.draw(&mut render_pass);
-                }
-            }
+            self.finish_playback();
+        }
+    }
+
+    /// 再生を終え、チェックサムを記録と比べられるように結果を残す
+    ///
+    /// 以降はキーボードの入力で操作できる。
+    fn finish_playback(&mut self) {
+        let Some(replay) = self.playback.take() else {
+            return;
+        };
+        self.playback_result = Some(PlaybackResult {
+            checksum: self.checksum(),
+            recorded_checksum: replay.checksum(),
+        });
+        self.pressed_keys.clear();
+        self.just_pressed_keys.clear();
+    }
+
+    pub fn is_playing_back(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// 終えた再生の結果を取り出す（まだ終えていないか、取り出し済みならNone）
+    pub fn take_playback_result(&mut self) -> Option<PlaybackResult> {
+        self.playback_result.take()
+    }
+
+    /// 再生が終わるまで（またはゲームが終了するまで）シーンを進め、チェックサムを返す
+    pub fn play_to_end(&mut self) -> u64 {
+        let step_time = self.timestep.step_time();
+        while self.is_playing_back() && self.running {
+            self.tick(step_time);
         }
+        self.checksum()
+    }
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+    /// ウィンドウサイズの変更に合わせて描画とシーンの画面の大きさを更新
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        if let Some(renderer) = &mut self.renderer {
+            renderer.resize(width, height);
+        }
+
+        // 記録・再生中に画面の大きさが変わると同じように進まなくなるので、ワールドは変えない
+        if self.recording.is_some() || self.playback.is_some() {
+            return;
+        }
+        let world_size = Vector2::new(width as f32, height as f32);
+        self.scene_context.set_world_size(world_size);
+        self.scenes.resize(world_size);
+    }
+
+    /// 画面（ゲーム空間）の大きさ
+    pub fn world_size(&self) -> Vector2 {
+        self.scene_context.world_size()
+    }
+
+    pub fn renderer(&self) -> Option<&Renderer> {
+        self.renderer.as_ref()
+    }
+
+    pub fn renderer_mut(&mut self) -> Option<&mut Renderer> {
+        self.renderer.as_mut()
+    }
+
+    pub fn high_scores(&self) -> &HighScoreTable {
+        self.scene_context.high_scores()
+    }
+
+    pub fn scenes(&self) -> &SceneStack {
+        &self.scenes
+    }
+
+    /// 一番上の見えているワールド（タイトルの背景やプレイ中の世界）
+    pub fn world(&self) -> Option<&World> {
+        self.scenes.world()
     }
 
     /// ゲームを終了
@@ -215,65 +403,223 @@ impl Game {
         self.delta_time = delta_time;
     }
 
-    /// アクターを取得
-    pub fn get_actor(&self, actor_id: u32) -> Option<&Actor> {
-        self.actors.get(&actor_id)
+    pub fn timestep(&self) -> &FixedTimestep {
+        &self.timestep
     }
 
-    /// アクターを可変で取得
-    pub fn get_actor_mut(&mut self, actor_id: u32) -> Option<&mut Actor> {
-        self.actors.get_mut(&actor_id)
+    /// 更新の頻度や追いつく回数の上限を変更するときに使う
+    pub fn timestep_mut(&mut self) -> &mut FixedTimestep {
+        &mut self.timestep
     }
 
     pub fn is_running(&self) -> bool {
         self.running
     }
 
-    pub fn create_laser(&mut self, pos: Vector2, rot: f32) -> u32 {
-        let laser_id = self.add_actor();
-        if let Some(laser_actor) = self.actors.get_mut(&laser_id) {
-            laser_actor.set_position(pos);
-            laser_actor.set_rotation(rot);
-            laser_actor.set_scale(Vector2::new(1.0, 1.0));
-            laser_actor.add_component(Box::new(Laser::new(&mut self.texture_manager)));
-        }
-        laser_id
+    pub fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    /// キーの割り当てを変更するときに使う
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
     }
 
     // キー押下状態を更新するメソッドを追加
     pub fn handle_keyboard_input(&mut self, keycode: VirtualKeyCode, pressed: bool) {
-        println!("Key event: {:?}, pressed: {}", keycode, pressed);
+        // 再生中は記録した入力だけを使う
+        if self.playback.is_some() {
+            return;
+        }
         if pressed {
             if !self.pressed_keys.contains(&keycode) {
                 self.pressed_keys.push(keycode);
-                println!("Current pressed keys: {:?}", self.pressed_keys);
+                self.just_pressed_keys.push(keycode);
             }
         } else {
             self.pressed_keys.retain(|&k| k != keycode);
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP_TIME: f32 = 1.0 / 60.0;
+    const SEED: u64 = 12345;
+
+    fn headless_game() -> Game {
+        Game::headless(Vector2::new(1024.0, 768.0), WaveConfig::default(), SEED)
+    }
 
-    pub fn create_ship(&mut self) -> Ship {
-        Ship::new(&mut self.texture_manager)
+    fn top_scene(game: &Game) -> &str {
+        game.scenes().top().map_or("", |scene| scene.name())
     }
 
-    pub fn setup_player_actor(&mut self, actor_id: u32) {
-        if let Some(actor) = self.actors.get_mut(&actor_id) {
-            actor.set_position(Vector2::new(100.0, 100.0));
-            actor.set_rotation(0.0);
-            actor.set_scale(Vector2::one());
+    /// 指定したキーを押し続けて、条件を満たすまで（最大max_ticks回）進める
+    fn step_until(
+        game: &mut Game,
+        keys: &[VirtualKeyCode],
+        max_ticks: u32,
+        condition: impl Fn(&Game) -> bool,
+    ) -> bool {
+        for _ in 0..max_ticks {
+            if condition(game) {
+                return true;
+            }
+            game.step(keys, STEP_TIME);
+        }
+        condition(game)
+    }
 
-            let ship = Ship::new(&mut self.texture_manager);
-            let sprite = SpriteComponent::new("Asteroid.png", 100, &mut self.texture_manager);
+    /// タイトルでEnterを押し、フェードが終わってプレイ中になるまで進める
+    fn start_playing(game: &mut Game) {
+        game.step(&[VirtualKeyCode::Return], STEP_TIME);
+        assert!(step_until(game, &[], 120, |game| {
+            top_scene(game) == "Playing" && game.scenes().fade_alpha() == 0.0
+        }));
+    }
 
-            let mut move_comp = MoveComponent::new(PI, 300.0);
-            let mut input_comp = InputComponent::new(300.0, PI);
-            input_comp.set_move_component(&mut move_comp);
+    #[test]
+    fn return_on_title_fades_into_playing() {
+        let mut game = headless_game();
+        assert_eq!(top_scene(&game), "Title");
+
+        game.step(&[VirtualKeyCode::Return], STEP_TIME);
+        game.step(&[], STEP_TIME);
+        // フェードアウトの間はまだタイトル
+        assert_eq!(top_scene(&game), "Title");
+        assert!(game.scenes().fade_alpha() > 0.0);
+
+        assert!(step_until(&mut game, &[], 120, |game| {
+            top_scene(game) == "Playing" && game.scenes().fade_alpha() == 0.0
+        }));
+        let stats = game.world().unwrap().stats();
+        assert_eq!((stats.score, stats.lives, stats.wave), (0, 3, 1));
+    }
+
+    #[test]
+    fn laser_destroys_asteroid_and_scores() {
+        let mut game = headless_game();
+        start_playing(&mut game);
+        let asteroids = game.world().unwrap().asteroid_count();
+        assert!(asteroids > 0);
+
+        // 回りながら撃ち続ければ、いずれ小惑星に当たる
+        let keys = [VirtualKeyCode::D, VirtualKeyCode::Space];
+        assert!(step_until(&mut game, &keys, 3_000, |game| {
+            game.world().is_some_and(|world| world.score() > 0)
+        }));
+        let world = game.world().unwrap();
+        assert_eq!(top_scene(&game), "Playing");
+        // 大きい小惑星は壊れると2〜3個に分かれる
+        assert!(world.asteroid_count() > asteroids);
+    }
+
+    #[test]
+    fn ship_destroyed_loses_a_life() {
+        let mut game = headless_game();
+        start_playing(&mut game);
+
+        assert!(step_until(&mut game, &[], 10_000, |game| {
+            game.world().is_some_and(|world| world.lives() < 3)
+        }));
+        let world = game.world().unwrap();
+        assert_eq!(world.lives(), 2);
+        assert!(!world.is_game_over());
+        // 宇宙船は壊れて復活を待っている
+        assert!(world.player_id().is_none());
+    }
+
+    #[test]
+    fn game_over_reaches_high_score_entry() {
+        let mut game = headless_game();
+        start_playing(&mut game);
+
+        let keys = [VirtualKeyCode::D, VirtualKeyCode::Space];
+        assert!(step_until(&mut game, &keys, 20_000, |game| {
+            top_scene(game) == "GameOver"
+        }));
+        let stats = game.world().unwrap().stats();
+        assert!(stats.game_over);
+        assert!(stats.score > 0);
+
+        // 得点があれば、空のハイスコア表には載るのでイニシャル入力に進む
+        assert!(step_until(&mut game, &[], 600, |game| {
+            top_scene(game) == "HighScoreEntry" && game.scenes().fade_alpha() == 0.0
+        }));
+        for key in [VirtualKeyCode::K, VirtualKeyCode::Key9, VirtualKeyCode::Z] {
+            game.step(&[key], STEP_TIME);
+            game.step(&[], STEP_TIME);
+        }
+        game.step(&[VirtualKeyCode::Return], STEP_TIME);
+        assert!(step_until(&mut game, &[], 120, |game| top_scene(game) == "Title"));
+
+        let entry = &game.high_scores().entries()[0];
+        assert_eq!(entry.initials(), "K9Z");
+        assert_eq!(entry.score(), stats.score);
+    }
+
+    /// 回りながら撃ち、ときどき加速する入力でtick回進めながら記録する
+    fn record(seed: u64, ticks: u32, fire: bool) -> Replay {
+        let mut game = Game::headless(Vector2::new(1024.0, 768.0), WaveConfig::default(), seed);
+        game.start_recording().unwrap();
+        start_playing(&mut game);
+        for tick in 0..ticks {
+            let keys: &[VirtualKeyCode] = if tick % 30 < 20 && fire {
+                &[VirtualKeyCode::D, VirtualKeyCode::Space]
+            } else if tick % 30 < 20 {
+                &[VirtualKeyCode::D]
+            } else {
+                &[VirtualKeyCode::W]
+            };
+            game.step(keys, STEP_TIME);
+        }
+        game.stop_recording().unwrap()
+    }
+
+    #[test]
+    fn replay_reproduces_checksum() {
+        let recorded = record(SEED, 5_000, true);
+        let replay = Replay::from_bytes(&recorded.to_bytes()).unwrap();
+        assert_eq!(replay, recorded);
+
+        let mut game = Game::headless_playback(replay, WaveConfig::default());
+        assert_eq!(game.config_hash(), recorded.recorded_config_hash());
+        let checksum = game.play_to_end();
+        assert!(!game.is_playing_back());
+        assert_eq!(Some(checksum), recorded.checksum());
+        let result = game.take_playback_result().unwrap();
+        assert_eq!(result.checksum, checksum);
+        assert_eq!(result.matches(), Some(true));
+        assert!(game.take_playback_result().is_none());
+    }
+
+    #[test]
+    fn checksum_depends_on_seed_and_input() {
+        let recorded = record(SEED, 2_000, true);
+        assert_ne!(
+            record(SEED + 1, 2_000, true).checksum(),
+            recorded.checksum()
+        );
+        assert_ne!(record(SEED, 2_000, false).checksum(), recorded.checksum());
+        assert_eq!(record(SEED, 2_000, true).checksum(), recorded.checksum());
+    }
 
-            actor.add_component(Box::new(sprite));
-            actor.add_component(Box::new(move_comp));
-            actor.add_component(Box::new(input_comp));
-            actor.add_component(Box::new(ship));
+    #[test]
+    fn headless_runs_thousands_of_ticks() {
+        let mut game = headless_game();
+        start_playing(&mut game);
+        for tick in 0..10_000 {
+            let keys: &[VirtualKeyCode] = if tick % 30 < 20 {
+                &[VirtualKeyCode::D, VirtualKeyCode::Space]
+            } else {
+                &[VirtualKeyCode::W]
+            };
+            game.step(keys, STEP_TIME);
+            assert!(game.is_running());
         }
+        assert!(!game.scenes().is_empty());
     }
 }
@@ -0,0 +1,84 @@
+use super::bitmap_font::TextAlign;
+use super::high_score_entry_scene::HighScoreEntryScene;
+use super::input_map::Action;
+use super::render_context::RenderContext;
+use super::scene::{Scene, SceneContext, SceneInput, SceneTransition};
+use super::sprite_instance::SpriteInstance;
+use super::stable_hasher::StableHasher;
+use super::title_scene::TitleScene;
+use super::vector2::Vector2;
+use std::hash::Hasher;
+
+/// ゲームオーバーを表示してから次の画面に進むまでの時間（秒）
+const GAME_OVER_TIME: f32 = 3.0;
+
+/// ゲームオーバー画面（プレイ中の画面の上に重ねて描く）
+///
+/// ハイスコアに載る得点ならイニシャル入力に、そうでなければタイトルに進む。
+pub struct GameOverScene {
+    score: u32,
+    wave: u32,
+    /// 次の画面に進むまでの残り時間
+    timer: f32,
+}
+
+impl GameOverScene {
+    pub fn new(score: u32, wave: u32) -> Self {
+        Self {
+            score,
+            wave,
+            timer: GAME_OVER_TIME,
+        }
+    }
+
+    fn next_scene(&self, ctx: &mut SceneContext) -> SceneTransition {
+        if ctx.high_scores().qualifies(self.score) {
+            SceneTransition::ReplaceAll(Box::new(HighScoreEntryScene::new(self.score, self.wave)))
+        } else {
+            SceneTransition::ReplaceAll(Box::new(TitleScene::new(ctx)))
+        }
+    }
+}
+
+impl Scene for GameOverScene {
+    fn name(&self) -> &str {
+        "GameOver"
+    }
+
+    fn process_input(&mut self, ctx: &mut SceneContext, input: &SceneInput) -> SceneTransition {
+        if input.just_pressed(Action::Confirm) {
+            self.next_scene(ctx)
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn update(&mut self, ctx: &mut SceneContext, delta_time: f32) -> SceneTransition {
+        self.timer -= delta_time;
+        if self.timer <= 0.0 {
+            self.next_scene(ctx)
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn render(&mut self, ctx: &mut RenderContext) {
+        let screen_size = ctx.screen_size();
+        ctx.draw_text(
+            "GAME OVER",
+            Vector2::new(screen_size.x * 0.5, screen_size.y * 0.4),
+            3.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+
+    fn write_checksum(&self, hasher: &mut StableHasher) {
+        hasher.write_u32(self.score);
+        hasher.write_u32(self.wave);
+    }
+}
@@ -0,0 +1,372 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 保存するスコアの数
+pub const MAX_HIGH_SCORES: usize = 10;
+/// ファイル形式のバージョン
+///
+/// - 1: `<イニシャル> <得点> <ウェーブ>`
+/// - 2: `<イニシャル> <得点> <ウェーブ> <日付>`
+const FILE_VERSION: u32 = 2;
+/// ファイルの1行目に書く識別子
+const FILE_HEADER: &str = "asteroid2-highscores";
+/// イニシャルの文字数
+const INITIALS_LENGTH: usize = 3;
+
+/// ハイスコア1件分
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighScoreEntry {
+    initials: String,
+    score: u32,
+    wave: u32,
+    /// 記録した日付（YYYY-MM-DD、古い形式から読んだ場合は空）
+    date: String,
+}
+
+impl HighScoreEntry {
+    /// 今日の日付で作成（イニシャルは英数字の大文字3文字に揃える）
+    pub fn new(initials: &str, score: u32, wave: u32) -> Self {
+        Self {
+            initials: normalize_initials(initials),
+            score,
+            wave,
+            date: today(),
+        }
+    }
+
+    pub fn initials(&self) -> &str {
+        &self.initials
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+}
+
+/// 得点の高い順に並べたハイスコア表
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighScoreTable {
+    entries: Vec<HighScoreEntry>,
+}
+
+impl HighScoreTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 保存先の標準のパス（ユーザーのデータディレクトリの下）
+    pub fn default_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("asteroid2").join("highscores.txt"))
+    }
+
+    /// ファイルから読み込む
+    ///
+    /// ファイルがなければ空の表を返す。壊れていれば、次の保存で上書きしないように
+    /// 別の名前に退避してから空の表を返す。
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        println!("Loading high scores: {:?}", path);
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::new(),
+            Err(e) => {
+                // 権限などの一時的な問題かもしれないので、ファイルはそのままにする
+                println!("Failed to read high scores: {:?} - Error: {}", path, e);
+                return Self::new();
+            }
+        };
+        let result = String::from_utf8(bytes)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Self::parse(&text));
+        match result {
+            Ok(table) => table,
+            Err(e) => {
+                println!("Corrupted high score file: {:?} - Error: {}", path, e);
+                Self::back_up(path);
+                Self::new()
+            }
+        }
+    }
+
+    /// 壊れたファイルを退避（`highscores.txt.1.bak`のように、前の退避を上書きしない番号を付ける）
+    fn back_up(path: &Path) {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let Some(backup) = (1..=u32::MAX)
+            .map(|number| path.with_file_name(format!("{}.{}.bak", file_name, number)))
+            .find(|backup| !backup.exists())
+        else {
+            return;
+        };
+        match fs::rename(path, &backup) {
+            Ok(()) => println!("Moved corrupted high scores to {:?}", backup),
+            Err(e) => println!("Failed to back up high scores: {:?} - Error: {}", backup, e),
+        }
+    }
+
+    /// ファイルの内容を解析（古いバージョンの形式も読める）
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        let header = lines.next().ok_or("empty file")?;
+        let version = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            [FILE_HEADER, version] => version
+                .parse::<u32>()
+                .map_err(|e| format!("bad version: {}", e))?,
+            _ => return Err(format!("bad header: `{}`", header)),
+        };
+        let field_count = match version {
+            1 => 3,
+            FILE_VERSION => 4,
+            _ => return Err(format!("unsupported version: {}", version)),
+        };
+
+        let mut table = Self::new();
+        for (index, line) in lines.enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != field_count {
+                return Err(format!(
+                    "entry {}: expects {} fields",
+                    index + 1,
+                    field_count
+                ));
+            }
+            let number = |field: &str| {
+                field
+                    .parse::<u32>()
+                    .map_err(|e| format!("entry {}: {}", index + 1, e))
+            };
+            table.entries.push(HighScoreEntry {
+                initials: normalize_initials(fields[0]),
+                score: number(fields[1])?,
+                wave: number(fields[2])?,
+                date: match fields.get(3) {
+                    Some(&date) if date != "-" => date.to_string(),
+                    _ => String::new(),
+                },
+            });
+        }
+
+        table.sort();
+        Ok(table)
+    }
+
+    /// ファイルの内容（常に最新のバージョンで書く）
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", FILE_HEADER, FILE_VERSION);
+        for entry in &self.entries {
+            let date = if entry.date.is_empty() {
+                "-"
+            } else {
+                &entry.date
+            };
+            text.push_str(&format!(
+                "{} {} {} {}\n",
+                entry.initials, entry.score, entry.wave, date
+            ));
+        }
+        text
+    }
+
+    /// ファイルに保存
+    ///
+    /// 一時ファイルに書いてから置き換えるので、途中で失敗しても元のファイルは壊れない。
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temp_path = path.with_extension("txt.tmp");
+        {
+            let mut file = fs::File::create(&temp_path)?;
+            file.write_all(self.to_text().as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&temp_path, path)
+    }
+
+    /// 得点の高い順のスコア
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    /// その得点が表に載るか
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_HIGH_SCORES
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// スコアを追加し、載った順位（0から数える）を返す
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        // 同点なら先に記録したほうを上にする
+        let rank = self
+            .entries
+            .partition_point(|other| other.score >= entry.score);
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    fn sort(&mut self) {
+        self.entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        self.entries.truncate(MAX_HIGH_SCORES);
+    }
+}
+
+/// 英数字の大文字3文字に揃える（足りなければ`-`で埋める）
+fn normalize_initials(initials: &str) -> String {
+    let mut normalized: String = initials
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .take(INITIALS_LENGTH)
+        .collect();
+    while normalized.len() < INITIALS_LENGTH {
+        normalized.push('-');
+    }
+    normalized
+}
+
+/// ユーザーのデータディレクトリ
+fn data_dir() -> Option<PathBuf> {
+    let from_env = |name: &str| env::var_os(name).filter(|value| !value.is_empty());
+    if cfg!(target_os = "windows") {
+        from_env("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        from_env("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        from_env("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| from_env("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    }
+}
+
+/// 今日の日付（UTC、YYYY-MM-DD）
+fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// 1970-01-01からの日数を年月日に変換
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: u32, wave: u32, date: &str) -> HighScoreEntry {
+        HighScoreEntry {
+            initials: initials.to_string(),
+            score,
+            wave,
+            date: date.to_string(),
+        }
+    }
+
+    /// テストごとに別の空のディレクトリ
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("asteroid2-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn version_1_loads_with_empty_date() {
+        let table =
+            HighScoreTable::parse("asteroid2-highscores 1\nABC 1200 4\nXY 300 2\n").unwrap();
+        assert_eq!(
+            table.entries(),
+            [entry("ABC", 1200, 4, ""), entry("XY-", 300, 2, "")]
+        );
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        for text in [
+            "",
+            "highscores 2\nABC 100 1 2024-01-01\n",
+            "asteroid2-highscores two\n",
+            "asteroid2-highscores 3\nABC 100 1 2024-01-01\n",
+            "asteroid2-highscores 2\nABC 100 1\n",
+            "asteroid2-highscores 1\nABC 100 1 2024-01-01\n",
+            "asteroid2-highscores 2\nABC lots 1 2024-01-01\n",
+        ] {
+            assert!(HighScoreTable::parse(text).is_err(), "accepted {:?}", text);
+        }
+    }
+
+    #[test]
+    fn entries_are_sorted_and_truncated() {
+        let mut text = String::from("asteroid2-highscores 2\n");
+        for score in 1..=15 {
+            text.push_str(&format!("AAA {} 1 2024-01-01\n", score * 100));
+        }
+        let table = HighScoreTable::parse(&text).unwrap();
+        let scores: Vec<u32> = table.entries().iter().map(|entry| entry.score()).collect();
+        assert_eq!(
+            scores,
+            (6..=15).rev().map(|score| score * 100).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut table = HighScoreTable::new();
+        table.entries = vec![
+            entry("ABC", 5000, 7, "2024-03-01"),
+            entry("K9Z", 5000, 6, ""),
+            entry("---", 10, 1, "2023-12-31"),
+        ];
+        assert_eq!(HighScoreTable::parse(&table.to_text()).unwrap(), table);
+    }
+
+    #[test]
+    fn corrupted_files_are_backed_up_without_overwriting() {
+        let dir = temp_dir("corrupted");
+        let path = dir.join("highscores.txt");
+        for contents in [&b"not a high score file"[..], &[0xff, 0xfe, 0x00][..]] {
+            fs::write(&path, contents).unwrap();
+            assert_eq!(HighScoreTable::load(&path), HighScoreTable::new());
+            assert!(!path.exists());
+        }
+        assert_eq!(
+            fs::read(dir.join("highscores.txt.1.bak")).unwrap(),
+            b"not a high score file"
+        );
+        assert_eq!(
+            fs::read(dir.join("highscores.txt.2.bak")).unwrap(),
+            [0xff, 0xfe, 0x00]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use super::bitmap_font::TextAlign;
+use super::high_score::HighScoreEntry;
+use super::input_map::Action;
+use super::render_context::RenderContext;
+use super::scene::{Scene, SceneContext, SceneInput, SceneTransition};
+use super::sprite_instance::SpriteInstance;
+use super::stable_hasher::StableHasher;
+use super::title_scene::TitleScene;
+use super::vector2::Vector2;
+use std::hash::Hasher;
+use winit::event::VirtualKeyCode;
+
+/// イニシャルの文字数
+const INITIALS_LENGTH: usize = 3;
+
+/// ハイスコアのイニシャル入力画面
+///
+/// A〜Zと0〜9で入力し、BackSpaceで1文字消して、Enterで記録する。
+pub struct HighScoreEntryScene {
+    score: u32,
+    wave: u32,
+    initials: String,
+}
+
+impl HighScoreEntryScene {
+    pub fn new(score: u32, wave: u32) -> Self {
+        Self {
+            score,
+            wave,
+            initials: String::new(),
+        }
+    }
+
+    /// イニシャルに使えるキーなら対応する文字を返す
+    fn key_to_char(key: VirtualKeyCode) -> Option<char> {
+        let code = key as u32;
+        let letters = VirtualKeyCode::A as u32..=VirtualKeyCode::Z as u32;
+        let digits = VirtualKeyCode::Key1 as u32..=VirtualKeyCode::Key0 as u32;
+        if letters.contains(&code) {
+            Some((b'A' + (code - letters.start()) as u8) as char)
+        } else if digits.contains(&code) {
+            // Key1〜Key9の後にKey0が並んでいる
+            Some((b'0' + ((code - digits.start() + 1) % 10) as u8) as char)
+        } else {
+            None
+        }
+    }
+}
+
+impl Scene for HighScoreEntryScene {
+    fn name(&self) -> &str {
+        "HighScoreEntry"
+    }
+
+    fn process_input(&mut self, ctx: &mut SceneContext, input: &SceneInput) -> SceneTransition {
+        for &key in &input.pressed_keys {
+            if key == VirtualKeyCode::Back {
+                self.initials.pop();
+            } else if let Some(c) = Self::key_to_char(key) {
+                if self.initials.len() < INITIALS_LENGTH {
+                    self.initials.push(c);
+                }
+            }
+        }
+
+        if input.just_pressed(Action::Confirm) && !self.initials.is_empty() {
+            ctx.submit_high_score(HighScoreEntry::new(&self.initials, self.score, self.wave));
+            return SceneTransition::ReplaceAll(Box::new(TitleScene::new(ctx)));
+        }
+        SceneTransition::None
+    }
+
+    fn render(&mut self, ctx: &mut RenderContext) {
+        let screen_size = ctx.screen_size();
+        let center_x = screen_size.x * 0.5;
+        ctx.draw_text(
+            "NEW HIGH SCORE",
+            Vector2::new(center_x, screen_size.y * 0.2),
+            3.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+        ctx.draw_text(
+            &format!("{}", self.score),
+            Vector2::new(center_x, screen_size.y * 0.35),
+            2.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+
+        // 入力していない文字は下線で示す
+        let initials: String = self
+            .initials
+            .chars()
+            .chain(std::iter::repeat('_'))
+            .take(INITIALS_LENGTH)
+            .collect();
+        ctx.draw_text(
+            &initials,
+            Vector2::new(center_x, screen_size.y * 0.5),
+            4.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+        ctx.draw_text(
+            "TYPE YOUR INITIALS AND PRESS ENTER",
+            Vector2::new(center_x, screen_size.y * 0.75),
+            1.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+    }
+
+    fn write_checksum(&self, hasher: &mut StableHasher) {
+        hasher.write_u32(self.score);
+        hasher.write_u32(self.wave);
+        hasher.write_str(&self.initials);
+    }
+}
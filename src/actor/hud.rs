@@ -0,0 +1,136 @@
+use super::bitmap_font::{BitmapFont, TextAlign};
+use super::sprite_instance::SpriteInstance;
+use super::texture::Texture;
+use super::vector2::Vector2;
+use super::world::GameStats;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+/// 画面端からの余白
+const MARGIN: f32 = 16.0;
+/// 得点の文字の大きさ
+const SCORE_SCALE: f32 = 1.5;
+/// ウェーブ番号の文字の大きさ
+const WAVE_SCALE: f32 = 1.0;
+/// 画面中央のメッセージの文字の大きさ
+const MESSAGE_SCALE: f32 = 3.0;
+/// 残機アイコンのスケール
+const LIFE_ICON_SCALE: f32 = 0.4;
+/// メッセージが消える前に薄くなっていく時間
+const MESSAGE_FADE_TIME: f32 = 0.5;
+
+/// 画面中央に出すメッセージ
+struct HudMessage {
+    text: String,
+    /// 消えるまでの残り時間（Noneなら消さない）
+    timer: Option<f32>,
+}
+
+/// 得点・残機・ウェーブとメッセージを画面に重ねて描くHUD
+///
+/// 画面座標（左上原点）で並べるので、ワールドのカメラとは関係なく同じ位置に出る。
+#[derive(Default)]
+pub struct Hud {
+    messages: Vec<HudMessage>,
+}
+
+impl Hud {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 画面中央にメッセージを出す（durationがNoneなら消すまで出し続ける）
+    pub fn show_message(&mut self, text: &str, duration: Option<f32>) {
+        self.messages.retain(|message| message.text != text);
+        self.messages.push(HudMessage {
+            text: text.to_string(),
+            timer: duration,
+        });
+    }
+
+    /// メッセージを消す
+    pub fn clear_messages(&mut self) {
+        self.messages.clear();
+    }
+
+    /// メッセージの残り時間を進め、時間切れのものを消す
+    pub fn update(&mut self, delta_time: f32) {
+        for message in &mut self.messages {
+            if let Some(timer) = &mut message.timer {
+                *timer -= delta_time;
+            }
+        }
+        self.messages
+            .retain(|message| message.timer.map_or(true, |timer| timer > 0.0));
+    }
+
+    /// HUDのスプライトを画面座標で並べる
+    pub fn layout(
+        &self,
+        stats: &GameStats,
+        font: &BitmapFont,
+        life_icon: Option<&Arc<Texture>>,
+        screen_size: Vector2,
+    ) -> Vec<(Arc<Texture>, SpriteInstance)> {
+        let white = SpriteInstance::WHITE;
+
+        // 得点（左上）
+        let mut instances = font.layout(
+            &stats.score.to_string(),
+            Vector2::new(MARGIN, MARGIN),
+            SCORE_SCALE,
+            white,
+            TextAlign::Left,
+        );
+
+        // 残機（得点の下に上向きの宇宙船を並べる）
+        if let Some(texture) = life_icon {
+            let icon_size =
+                Vector2::new(texture.size.0 as f32, texture.size.1 as f32) * LIFE_ICON_SCALE;
+            let top = MARGIN + font.line_height() * SCORE_SCALE + 4.0;
+            for i in 0..stats.lives {
+                let center = Vector2::new(
+                    MARGIN + icon_size.x * (i as f32 + 0.5),
+                    top + icon_size.y / 2.0,
+                );
+                let instance = SpriteInstance::new(
+                    center,
+                    -PI / 2.0,
+                    Vector2::one() * LIFE_ICON_SCALE,
+                    texture.size,
+                );
+                instances.push((texture.clone(), instance));
+            }
+        }
+
+        // ウェーブ番号（右上）
+        if stats.wave > 0 {
+            instances.extend(font.layout(
+                &format!("WAVE {}", stats.wave),
+                Vector2::new(screen_size.x - MARGIN, MARGIN),
+                WAVE_SCALE,
+                white,
+                TextAlign::Right,
+            ));
+        }
+
+        // メッセージ（画面中央に上から積む）
+        let line_height = font.line_height() * MESSAGE_SCALE;
+        let mut y = (screen_size.y - line_height * self.messages.len() as f32) / 2.0;
+        for message in &self.messages {
+            let alpha = message
+                .timer
+                .map_or(1.0, |timer| (timer / MESSAGE_FADE_TIME).min(1.0));
+            instances.extend(font.layout(
+                &message.text,
+                Vector2::new(screen_size.x / 2.0, y),
+                MESSAGE_SCALE,
+                [1.0, 1.0, 1.0, alpha],
+                TextAlign::Center,
+            ));
+            y += line_height;
+        }
+
+        instances
+    }
+}
@@ -1,74 +1,73 @@
-use super::actor::Actor;
-use super::component::{Component, ComponentBase};
-use super::move_component::MoveComponent;
-use winit::event::VirtualKeyCode;
+use super::component::{Component, ComponentContext};
+use super::input_map::Action;
+use super::rigid_body_component::RigidBodyComponent;
 
+/// 入力された操作に応じて同じアクターのRigidBodyComponentに力を加える
 pub struct InputComponent {
-    base: ComponentBase,
-    max_forward_speed: f32,
-    max_angular_speed: f32,
-    move_component: Option<*mut MoveComponent>,
+    /// 前進するときに加える力
+    thrust: f32,
+    /// 回転するときに加える回転力
+    torque: f32,
+    /// 直前の入力で推力を出していたか
+    thrusting: bool,
 }
 
 impl InputComponent {
-    pub fn new(max_forward_speed: f32, max_angular_speed: f32) -> Self {
+    pub fn new(thrust: f32, torque: f32) -> Self {
         Self {
-            base: ComponentBase::new(),
-            max_forward_speed,
-            max_angular_speed,
-            move_component: None,
+            thrust,
+            torque,
+            thrusting: false,
         }
     }
 
-    pub fn set_move_component(&mut self, move_comp: &mut MoveComponent) {
-        self.move_component = Some(move_comp as *mut MoveComponent);
+    pub fn thrust(&self) -> f32 {
+        self.thrust
     }
 
-    pub fn process_input(&mut self, keys: &[VirtualKeyCode]) {
-        // println!("InputComponent processing keys: {:?}", keys);
-        if let Some(move_ptr) = self.move_component {
-            let move_comp = unsafe { &mut *move_ptr };
-
-            // 前進/後退の処理
-            if keys.contains(&VirtualKeyCode::W) {
-                println!("W key pressed - moving forward");
-                move_comp.set_forward_speed(self.max_forward_speed);
-            } else if keys.contains(&VirtualKeyCode::S) {
-                println!("S key pressed - moving backward");
-                move_comp.set_forward_speed(-self.max_forward_speed);
-            } else {
-                move_comp.set_forward_speed(0.0);
-            }
+    pub fn set_thrust(&mut self, thrust: f32) {
+        self.thrust = thrust;
+    }
 
-            // 回転の処理
-            if keys.contains(&VirtualKeyCode::D) {
-                println!("D key pressed - rotating right");
-                move_comp.set_angular_speed(self.max_angular_speed);
-            } else if keys.contains(&VirtualKeyCode::A) {
-                println!("A key pressed - rotating left");
-                move_comp.set_angular_speed(-self.max_angular_speed);
-            } else {
-                move_comp.set_angular_speed(0.0);
-            }
-        }
+    pub fn torque(&self) -> f32 {
+        self.torque
     }
-}
 
-impl Component for InputComponent {
-    fn update(&mut self, _delta_time: f32) {
-        // キー入力の処理は別途実装が必要
+    pub fn set_torque(&mut self, torque: f32) {
+        self.torque = torque;
     }
 
-    fn set_owner(&mut self, owner: &mut Actor) {
-        self.base.set_owner(owner);
+    /// 直前の入力で推力を出していたか
+    pub fn is_thrusting(&self) -> bool {
+        self.thrusting
     }
+}
 
-    fn owner(&self) -> Option<&Actor> {
-        self.base.owner()
+impl Component for InputComponent {
+    fn update(&mut self, _ctx: &mut ComponentContext, _delta_time: f32) {
+        // キー入力はprocess_inputで処理する
     }
 
-    fn owner_mut(&mut self) -> Option<&mut Actor> {
-        self.base.owner_mut()
+    fn process_input(&mut self, ctx: &mut ComponentContext, actions: &[Action]) {
+        let forward = ctx.owner().forward();
+        self.thrusting = false;
+        if let Some(body) = ctx.get_component_mut::<RigidBodyComponent>() {
+            // 前進/後退の処理（キーを離しても慣性で進み続ける）
+            if actions.contains(&Action::Thrust) {
+                body.add_force(forward * self.thrust);
+                self.thrusting = true;
+            } else if actions.contains(&Action::Reverse) {
+                body.add_force(forward * -self.thrust);
+                self.thrusting = true;
+            }
+
+            // 回転の処理
+            if actions.contains(&Action::RotateRight) {
+                body.add_torque(self.torque);
+            } else if actions.contains(&Action::RotateLeft) {
+                body.add_torque(-self.torque);
+            }
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -0,0 +1,98 @@
+use super::stable_hasher::StableHasher;
+use std::hash::Hasher;
+use winit::event::VirtualKeyCode;
+
+/// ゲーム内の操作
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// 前進
+    Thrust,
+    /// 後退
+    Reverse,
+    /// 左回転
+    RotateLeft,
+    /// 右回転
+    RotateRight,
+    /// レーザー発射
+    Fire,
+    /// 一時停止と再開
+    Pause,
+    /// メニューの決定
+    Confirm,
+    /// ゲームをやめる
+    Quit,
+}
+
+/// キーと操作の対応表
+///
+/// 1つの操作に複数のキーを割り当てられる。
+#[derive(Debug, Clone)]
+pub struct InputMap {
+    bindings: Vec<(VirtualKeyCode, Action)>,
+}
+
+impl Default for InputMap {
+    /// 標準のキー割り当て（WASDで移動、Spaceで発射、Esc/Pで一時停止、Enterで決定、Qで終了）
+    fn default() -> Self {
+        let mut map = Self::new();
+        map.bind(VirtualKeyCode::W, Action::Thrust);
+        map.bind(VirtualKeyCode::S, Action::Reverse);
+        map.bind(VirtualKeyCode::A, Action::RotateLeft);
+        map.bind(VirtualKeyCode::D, Action::RotateRight);
+        map.bind(VirtualKeyCode::Space, Action::Fire);
+        map.bind(VirtualKeyCode::Escape, Action::Pause);
+        map.bind(VirtualKeyCode::P, Action::Pause);
+        map.bind(VirtualKeyCode::Return, Action::Confirm);
+        map.bind(VirtualKeyCode::Q, Action::Quit);
+        map
+    }
+}
+
+impl InputMap {
+    /// 何も割り当てられていない対応表を作成
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// キーに操作を割り当てる
+    pub fn bind(&mut self, key: VirtualKeyCode, action: Action) {
+        if !self.bindings.contains(&(key, action)) {
+            self.bindings.push((key, action));
+        }
+    }
+
+    /// キーの割り当てを解除
+    pub fn unbind(&mut self, key: VirtualKeyCode) {
+        self.bindings.retain(|&(k, _)| k != key);
+    }
+
+    /// 操作に割り当てられたキー
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |&&(_, a)| a == action)
+            .map(|&(k, _)| k)
+    }
+
+    /// 押されているキーから、行われている操作を求める（重複なし）
+    pub fn actions(&self, pressed_keys: &[VirtualKeyCode]) -> Vec<Action> {
+        let mut actions = Vec::new();
+        for &(key, action) in &self.bindings {
+            if pressed_keys.contains(&key) && !actions.contains(&action) {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// 全ての割り当てを登録順にハッシュに加える（リプレイの設定の照合に使う）
+    pub fn write_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(self.bindings.len() as u64);
+        for &(key, action) in &self.bindings {
+            hasher.write_u32(key as u32);
+            hasher.write_u32(action as u32);
+        }
+    }
+}
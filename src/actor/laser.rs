@@ -1,70 +1,48 @@
-use super::actor::Actor;
-use super::component::{Component, ComponentBase};
-use super::game::Game;
-use super::sprite_component::SpriteComponent;
-use super::texture::TextureManager;
+use super::actor::ActorStatus;
+use super::asteroid::Asteroid;
+use super::collision::CollisionEvent;
+use super::component::{Component, ComponentContext};
 use super::vector2::Vector2;
-use std::f32::consts::PI;
 
+/// 一定時間直進して消えるレーザー
+///
+/// スプライトはWorld::create_laserで同じアクターに付けられる。
 pub struct Laser {
-    base: ComponentBase,
     death_timer: f32,
     forward_speed: f32,
+    /// 発射した物体から引き継いだ速度
+    base_velocity: Vector2,
 }
 
 impl Laser {
-    pub fn new(texture_manager: &mut TextureManager) -> Self {
-        let mut laser = Self {
-            base: ComponentBase::new(),
+    pub fn new(base_velocity: Vector2) -> Self {
+        Self {
             death_timer: 1.0,     // レーザーの生存時間
             forward_speed: 800.0, // レーザーの速度
-        };
-
-        // スプライトコンポーネントを追加
-        let sprite = SpriteComponent::new("Laser.png", 100, texture_manager);
-        laser.add_component(Box::new(sprite));
-
-        laser
-    }
-
-    pub fn add_component(&mut self, component: Box<dyn Component>) {
-        if let Some(actor) = self.owner_mut() {
-            actor.add_component(component);
+            base_velocity,
         }
     }
 }
 
 impl Component for Laser {
-    fn update(&mut self, delta_time: f32) {
-        let forward_speed = self.forward_speed;
-        let should_deactivate = {
-            let death_timer = self.death_timer;
-            self.death_timer = death_timer - delta_time;
-            self.death_timer <= 0.0
-        };
+    fn update(&mut self, ctx: &mut ComponentContext, delta_time: f32) {
+        self.death_timer -= delta_time;
 
-        if let Some(actor) = self.owner_mut() {
-            let rotation = actor.rotation();
-            let forward = Vector2::new(rotation.cos(), rotation.sin());
-            let pos = actor.position();
-            actor.set_position(pos + forward * forward_speed * delta_time);
+        let actor = ctx.owner_mut();
+        let forward = actor.forward();
+        let pos = actor.position();
+        actor.set_position(pos + (forward * self.forward_speed + self.base_velocity) * delta_time);
 
-            if should_deactivate {
-                actor.set_active(false);
-            }
+        if self.death_timer <= 0.0 {
+            actor.set_status(ActorStatus::Dead);
         }
     }
 
-    fn set_owner(&mut self, owner: &mut Actor) {
-        self.base.set_owner(owner);
-    }
-
-    fn owner(&self) -> Option<&Actor> {
-        self.base.owner()
-    }
-
-    fn owner_mut(&mut self) -> Option<&mut Actor> {
-        self.base.owner_mut()
+    fn on_collision(&mut self, ctx: &mut ComponentContext, event: &CollisionEvent) {
+        // 小惑星に当たったら消える
+        if event.other_has::<Asteroid>() {
+            ctx.owner_mut().set_status(ActorStatus::Dead);
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -42,6 +42,22 @@ impl Math {
         }
     }
 
+    /// 値を0以上size未満の範囲に折り返す
+    pub fn wrap(value: f32, size: f32) -> f32 {
+        let result = value.rem_euclid(size);
+        // rem_euclidは丸め誤差でsizeを返すことがある
+        if result >= size {
+            0.0
+        } else {
+            result
+        }
+    }
+
+    /// 周期sizeで折り返す空間での差分（-size/2からsize/2の範囲）
+    pub fn wrapped_delta(delta: f32, size: f32) -> f32 {
+        delta - size * (delta / size).round()
+    }
+
     /// 2つのベクトル間の角度を計算（ラジアン）
     pub fn angle_between(v1: Vector2, v2: Vector2) -> f32 {
         (v2.y.atan2(v2.x) - v1.y.atan2(v1.x)).abs()
@@ -1,28 +1,91 @@
+#[allow(clippy::module_inception)]
 pub mod actor;
+pub mod actor_id;
+pub mod actor_storage;
 pub mod asteroid;
+pub mod bitmap_font;
 pub mod circle_component;
+pub mod collision;
+pub mod collision_layers;
+pub mod commands;
 pub mod component;
+pub mod debris;
+pub mod fixed_timestep;
 pub mod game;
+pub mod game_over_scene;
+pub mod high_score;
+pub mod high_score_entry_scene;
+pub mod hud;
 pub mod input_component;
+pub mod input_map;
 pub mod laser;
 pub mod math;
 pub mod move_component;
+pub mod paused_scene;
+pub mod player;
+pub mod playing_scene;
 pub mod random;
+pub mod render_context;
+pub mod renderer;
+pub mod replay;
+pub mod rigid_body_component;
+pub mod scene;
+pub mod score;
 pub mod ship;
+pub mod spatial_hash;
+pub mod sprite_batch;
 pub mod sprite_component;
+pub mod sprite_instance;
+pub mod sprite_registry;
+pub mod stable_hasher;
 pub mod texture;
+pub mod title_scene;
 pub mod vector2;
+pub mod wave;
+pub mod world;
+pub mod wrap_component;
 
-pub use self::actor::Actor;
-pub use self::asteroid::Asteroid;
+pub use self::actor::{Actor, ActorState, ActorStatus};
+pub use self::actor_id::ActorId;
+pub use self::actor_storage::ActorStorage;
+pub use self::asteroid::{Asteroid, AsteroidSize};
+pub use self::bitmap_font::{BitmapFont, FontPage, TextAlign};
 pub use self::circle_component::CircleComponent;
-pub use self::component::Component;
+pub use self::collision::{Collision, CollisionBody, CollisionEvent, CollisionSystem};
+pub use self::collision_layers::{CollisionLayers, LayerMask};
+pub use self::commands::{Command, Commands};
+pub use self::component::{Component, ComponentContext};
+pub use self::debris::Debris;
+pub use self::fixed_timestep::FixedTimestep;
+pub use self::game_over_scene::GameOverScene;
+pub use self::high_score::{HighScoreEntry, HighScoreTable};
+pub use self::high_score_entry_scene::HighScoreEntryScene;
+pub use self::hud::Hud;
 pub use self::input_component::InputComponent;
+pub use self::input_map::{Action, InputMap};
 pub use self::laser::Laser;
 pub use self::math::Math;
 pub use self::move_component::MoveComponent;
-pub use self::random::Random;
+pub use self::paused_scene::PausedScene;
+pub use self::player::{Player, PlayerState};
+pub use self::playing_scene::PlayingScene;
+pub use self::random::{Random, RandomStream, RandomStreams};
+pub use self::render_context::RenderContext;
+pub use self::renderer::Renderer;
+pub use self::replay::{Replay, ReplayTick};
+pub use self::rigid_body_component::RigidBodyComponent;
+pub use self::scene::{Scene, SceneContext, SceneInput, SceneStack, SceneTransition};
+pub use self::score::{EnemyKind, Score, ScoreConfig, ScoreSource};
 pub use self::ship::Ship;
+pub use self::spatial_hash::SpatialHash;
+pub use self::sprite_batch::SpriteBatch;
 pub use self::sprite_component::SpriteComponent;
+pub use self::sprite_instance::{CameraUniform, SpriteInstance};
+pub use self::sprite_registry::SpriteRegistry;
+pub use self::stable_hasher::StableHasher;
 pub use self::texture::TextureManager;
+pub use self::title_scene::TitleScene;
 pub use self::vector2::Vector2;
+pub use self::wave::{WaveConfig, WaveManager, WaveParams};
+pub use self::world::{GameEvent, GameStats, World};
+pub use self::wrap_component::WrapComponent;
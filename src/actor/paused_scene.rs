@@ -0,0 +1,56 @@
+use super::bitmap_font::TextAlign;
+use super::input_map::Action;
+use super::render_context::RenderContext;
+use super::scene::{Scene, SceneContext, SceneInput, SceneTransition};
+use super::sprite_instance::SpriteInstance;
+use super::title_scene::TitleScene;
+use super::vector2::Vector2;
+
+/// 一時停止画面（プレイ中の画面の上に重ねて描く）
+#[derive(Default)]
+pub struct PausedScene;
+
+impl PausedScene {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Scene for PausedScene {
+    fn name(&self) -> &str {
+        "Paused"
+    }
+
+    fn process_input(&mut self, ctx: &mut SceneContext, input: &SceneInput) -> SceneTransition {
+        if input.just_pressed(Action::Pause) {
+            SceneTransition::Pop
+        } else if input.just_pressed(Action::Quit) {
+            SceneTransition::ReplaceAll(Box::new(TitleScene::new(ctx)))
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn render(&mut self, ctx: &mut RenderContext) {
+        let screen_size = ctx.screen_size();
+        ctx.draw_rect(Vector2::zero(), screen_size, [0.0, 0.0, 0.0, 0.5]);
+        ctx.draw_text(
+            "PAUSED",
+            Vector2::new(screen_size.x * 0.5, screen_size.y * 0.4),
+            3.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+        ctx.draw_text(
+            "P: RESUME  Q: QUIT TO TITLE",
+            Vector2::new(screen_size.x * 0.5, screen_size.y * 0.55),
+            1.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,101 @@
+/// プレイヤーの状態
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlayerState {
+    /// 宇宙船が画面にいる
+    Alive,
+    /// 宇宙船が壊され、復活を待っている（残り時間）
+    Respawning(f32),
+    /// 残機がなくなった
+    GameOver,
+}
+
+/// 残機と、宇宙船の破壊・復活の進行を管理する
+///
+/// 宇宙船が壊されると残機を1つ減らし、一定時間後、出現位置の周りに
+/// 小惑星がいなくなったときに復活させる。残機が0になるとゲームオーバー。
+pub struct Player {
+    state: PlayerState,
+    lives: u32,
+    /// 壊されてから復活するまでの時間
+    respawn_delay: f32,
+    /// 復活してから無敵でいる時間
+    invulnerable_time: f32,
+}
+
+impl Player {
+    pub fn new(lives: u32) -> Self {
+        Self {
+            state: PlayerState::Alive,
+            lives,
+            respawn_delay: 2.0,
+            invulnerable_time: 3.0,
+        }
+    }
+
+    pub fn state(&self) -> PlayerState {
+        self.state
+    }
+
+    /// 残機（画面にいる宇宙船を含む）
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    /// 残機を1つ増やす
+    pub fn add_life(&mut self) {
+        if self.state != PlayerState::GameOver {
+            self.lives += 1;
+        }
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.state == PlayerState::GameOver
+    }
+
+    pub fn respawn_delay(&self) -> f32 {
+        self.respawn_delay
+    }
+
+    pub fn set_respawn_delay(&mut self, delay: f32) {
+        self.respawn_delay = delay;
+    }
+
+    pub fn invulnerable_time(&self) -> f32 {
+        self.invulnerable_time
+    }
+
+    pub fn set_invulnerable_time(&mut self, time: f32) {
+        self.invulnerable_time = time;
+    }
+
+    /// 宇宙船の状態を受け取って進行を更新し、宇宙船を復活させるときはtrueを返す
+    ///
+    /// ship_aliveは宇宙船が画面にいるか、spawn_area_clearは出現位置の周りに
+    /// 小惑星がいないか。
+    pub fn update(&mut self, delta_time: f32, ship_alive: bool, spawn_area_clear: bool) -> bool {
+        match self.state {
+            PlayerState::Alive => {
+                if !ship_alive {
+                    self.lives = self.lives.saturating_sub(1);
+                    self.state = if self.lives == 0 {
+                        PlayerState::GameOver
+                    } else {
+                        PlayerState::Respawning(self.respawn_delay)
+                    };
+                }
+                false
+            }
+            PlayerState::Respawning(timer) => {
+                let timer = timer - delta_time;
+                // 時間が経っても、出現位置が空くまでは待つ
+                if timer > 0.0 || !spawn_area_clear {
+                    self.state = PlayerState::Respawning(timer.max(0.0));
+                    return false;
+                }
+                self.state = PlayerState::Alive;
+                true
+            }
+            PlayerState::GameOver => false,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::game_over_scene::GameOverScene;
+use super::hud::Hud;
+use super::input_map::Action;
+use super::paused_scene::PausedScene;
+use super::render_context::RenderContext;
+use super::scene::{Scene, SceneContext, SceneInput, SceneTransition};
+use super::vector2::Vector2;
+use super::world::{GameEvent, World};
+
+/// ゲーム開始時の残機
+const STARTING_LIVES: u32 = 3;
+/// 残機がなくなってからゲームオーバー画面を出すまでの時間（秒）
+const GAME_OVER_DELAY: f32 = 1.5;
+
+/// プレイ中の画面
+pub struct PlayingScene {
+    world: World,
+    /// 得点・残機・ウェーブの表示とメッセージ
+    hud: Hud,
+    /// 今回のフレームで押されている操作
+    actions: Vec<Action>,
+    /// ゲームオーバー画面を出すまでの残り時間（ゲームオーバーでなければNone）
+    game_over_timer: Option<f32>,
+}
+
+impl PlayingScene {
+    pub fn new(ctx: &mut SceneContext) -> Self {
+        let seed = ctx.next_seed();
+        let mut world = World::new(ctx.world_size(), ctx.wave_config().clone(), seed);
+        world.start_player(STARTING_LIVES);
+        Self {
+            world,
+            hud: Hud::new(),
+            actions: Vec::new(),
+            game_over_timer: None,
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// ワールドで起きた出来事をHUDのメッセージにする
+    fn handle_events(&mut self) {
+        for event in self.world.take_events() {
+            match event {
+                GameEvent::WaveStarted(wave) => {
+                    self.hud.show_message(&format!("WAVE {}", wave), Some(2.0))
+                }
+                GameEvent::ExtraLife => self.hud.show_message("EXTRA LIFE", Some(1.5)),
+                GameEvent::GameOver => self.game_over_timer = Some(GAME_OVER_DELAY),
+                GameEvent::ShipDestroyed => {}
+            }
+        }
+    }
+}
+
+impl Scene for PlayingScene {
+    fn name(&self) -> &str {
+        "Playing"
+    }
+
+    fn process_input(&mut self, _ctx: &mut SceneContext, input: &SceneInput) -> SceneTransition {
+        if input.just_pressed(Action::Pause) && self.game_over_timer.is_none() {
+            self.actions.clear();
+            return SceneTransition::Push(Box::new(PausedScene::new()));
+        }
+        self.actions = input.actions.clone();
+        SceneTransition::None
+    }
+
+    fn update(&mut self, _ctx: &mut SceneContext, delta_time: f32) -> SceneTransition {
+        self.world.update(delta_time, &self.actions);
+        self.handle_events();
+        self.hud.update(delta_time);
+
+        if let Some(timer) = self.game_over_timer {
+            let timer = timer - delta_time;
+            if timer <= 0.0 {
+                self.game_over_timer = None;
+                let stats = self.world.stats();
+                return SceneTransition::Push(Box::new(GameOverScene::new(
+                    stats.score,
+                    stats.wave,
+                )));
+            }
+            self.game_over_timer = Some(timer);
+        }
+        SceneTransition::None
+    }
+
+    fn render(&mut self, ctx: &mut RenderContext) {
+        ctx.draw_world(&mut self.world);
+        ctx.draw_hud(&self.hud, &self.world);
+    }
+
+    fn resize(&mut self, world_size: Vector2) {
+        self.world.set_world_size(world_size);
+    }
+
+    fn world(&self) -> Option<&World> {
+        Some(&self.world)
+    }
+}
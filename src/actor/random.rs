@@ -1,43 +1,125 @@
 use super::vector2::Vector2;
-use rand::{thread_rng, Rng};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::f32::consts::PI;
 
-/// ランダム値生成ユーティリティ
-pub struct Random;
+/// シードから再現できるランダム値生成
+///
+/// 同じシードからはどの環境でも同じ列が得られる。リプレイにシードを保存するので、
+/// randの更新でアルゴリズムが変わりうるStdRngではなく、アルゴリズムを固定したChaCha8を使う。
+pub struct Random {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
 
 impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// 作成に使ったシード
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     /// 指定範囲の浮動小数点数をランダムに生成
-    pub fn float_range(min: f32, max: f32) -> f32 {
-        thread_rng().gen_range(min..=max)
+    pub fn float_range(&mut self, min: f32, max: f32) -> f32 {
+        self.rng.gen_range(min..=max)
     }
 
     /// 指定範囲の整数をランダムに生成
-    pub fn int_range(min: i32, max: i32) -> i32 {
-        thread_rng().gen_range(min..=max)
+    pub fn int_range(&mut self, min: i32, max: i32) -> i32 {
+        self.rng.gen_range(min..=max)
     }
 
     /// -1.0から1.0の範囲でランダムな値を生成
-    pub fn float() -> f32 {
-        Self::float_range(-1.0, 1.0)
+    pub fn float(&mut self) -> f32 {
+        self.float_range(-1.0, 1.0)
+    }
+
+    /// ランダムな64ビットの値を生成（別のRandomのシードに使う）
+    pub fn next_u64(&mut self) -> u64 {
+        self.rng.gen()
     }
 
     /// ランダムな方向のベクトルを生成
-    pub fn vector() -> Vector2 {
-        let angle = Self::float_range(0.0, PI * 2.0);
+    pub fn vector(&mut self) -> Vector2 {
+        let angle = self.float_range(0.0, PI * 2.0);
         Vector2::new(angle.cos(), angle.sin())
     }
 
     /// 指定された長さのランダムな方向のベクトルを生成
-    pub fn vector_with_length(length: f32) -> Vector2 {
-        let v = Self::vector();
+    pub fn vector_with_length(&mut self, length: f32) -> Vector2 {
+        let v = self.vector();
         v * length
     }
 
     /// 指定された範囲内のランダムなベクトルを生成
-    pub fn vector_in_rect(min_x: f32, max_x: f32, min_y: f32, max_y: f32) -> Vector2 {
+    pub fn vector_in_rect(&mut self, min_x: f32, max_x: f32, min_y: f32, max_y: f32) -> Vector2 {
         Vector2::new(
-            Self::float_range(min_x, max_x),
-            Self::float_range(min_y, max_y),
+            self.float_range(min_x, max_x),
+            self.float_range(min_y, max_y),
         )
     }
 }
+
+/// 用途ごとに分けたランダム値の列
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RandomStream {
+    /// 小惑星の出現や分裂などゲームの進行に関わるもの
+    Spawning,
+    /// 敵の行動
+    Ai,
+    /// 破片や回転など見た目だけのもの
+    Vfx,
+}
+
+impl RandomStream {
+    const ALL: [RandomStream; 3] = [RandomStream::Spawning, RandomStream::Ai, RandomStream::Vfx];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 1つのシードから用途ごとの列を作ったランダム値生成
+///
+/// 列を分けておくことで、見た目だけの乱数を増減してもゲームの進行は変わらない。
+pub struct RandomStreams {
+    seed: u64,
+    streams: Vec<Random>,
+}
+
+impl RandomStreams {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: RandomStream::ALL
+                .iter()
+                .map(|stream| Random::new(Self::stream_seed(seed, *stream)))
+                .collect(),
+        }
+    }
+
+    /// 用途ごとの列のシード（SplitMix64で元のシードと用途を混ぜる）
+    fn stream_seed(seed: u64, stream: RandomStream) -> u64 {
+        let mut z =
+            seed.wrapping_add((stream.index() as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// 作成に使ったシード
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 指定した用途の列
+    pub fn get(&mut self, stream: RandomStream) -> &mut Random {
+        &mut self.streams[stream.index()]
+    }
+}
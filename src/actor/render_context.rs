@@ -0,0 +1,169 @@
+use super::bitmap_font::{BitmapFont, TextAlign};
+use super::hud::Hud;
+use super::sprite_batch::SpriteBatch;
+use super::sprite_component::SpriteComponent;
+use super::sprite_instance::SpriteInstance;
+use super::texture::TextureManager;
+use super::vector2::Vector2;
+use super::world::World;
+use super::wrap_component::WrapComponent;
+
+/// 塗りつぶしに使う白いテクスチャの名前
+pub const WHITE_TEXTURE: &str = "__white";
+
+/// シーンが描画するときに使う描画先
+///
+/// ワールドのスプライトはワールドのカメラで、文字列や矩形は画面座標で描かれる。
+pub struct RenderContext<'a> {
+    world_batch: &'a mut SpriteBatch,
+    screen_batch: &'a mut SpriteBatch,
+    texture_manager: &'a mut TextureManager,
+    font: Option<&'a BitmapFont>,
+    screen_size: Vector2,
+    /// 画面座標で描くものの描画順（上のシーンほど大きい）
+    screen_order: i32,
+    /// ワールドのスプライトを前回の更新から補間する割合
+    interpolation: f32,
+}
+
+impl<'a> RenderContext<'a> {
+    pub fn new(
+        world_batch: &'a mut SpriteBatch,
+        screen_batch: &'a mut SpriteBatch,
+        texture_manager: &'a mut TextureManager,
+        font: Option<&'a BitmapFont>,
+        screen_size: Vector2,
+    ) -> Self {
+        Self {
+            world_batch,
+            screen_batch,
+            texture_manager,
+            font,
+            screen_size,
+            screen_order: 0,
+            interpolation: 1.0,
+        }
+    }
+
+    /// 画面の大きさ
+    pub fn screen_size(&self) -> Vector2 {
+        self.screen_size
+    }
+
+    pub fn font(&self) -> Option<&BitmapFont> {
+        self.font
+    }
+
+    /// 画面座標で描くものの描画順を設定
+    pub fn set_screen_order(&mut self, order: i32) {
+        self.screen_order = order;
+    }
+
+    pub fn interpolation(&self) -> f32 {
+        self.interpolation
+    }
+
+    /// ワールドのスプライトを前回の更新から補間する割合を設定（1.0なら補間しない）
+    pub fn set_interpolation(&mut self, alpha: f32) {
+        self.interpolation = alpha;
+    }
+
+    /// ワールドのスプライトを描画順に描く
+    pub fn draw_world(&mut self, world: &mut World) {
+        world.resolve_sprite_textures(self.texture_manager);
+
+        let world_size = world.world_size();
+        for actor_id in world.sprite_actors() {
+            let Some(actor) = world.get_actor(actor_id) else {
+                continue;
+            };
+            if let Some(sprite) = actor.get_component::<SpriteComponent>() {
+                if let (Some(texture), Some(instance)) =
+                    (sprite.texture(), sprite.instance(actor, self.interpolation))
+                {
+                    self.world_batch
+                        .push(sprite.draw_order(), texture, instance);
+
+                    // 画面端にかかっている折り返すスプライトは反対側にも描く
+                    if actor.get_component::<WrapComponent>().is_some() {
+                        let extent =
+                            Vector2::new(instance.size[0], instance.size[1]).length() / 2.0;
+                        for offset in WrapComponent::ghost_offsets(
+                            actor.interpolated_position(self.interpolation),
+                            extent,
+                            world_size,
+                        ) {
+                            let mut ghost = instance;
+                            ghost.position[0] += offset.x;
+                            ghost.position[1] += offset.y;
+                            self.world_batch.push(sprite.draw_order(), texture, ghost);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 文字列を描く（positionは画面座標で1行目の上端）
+    pub fn draw_text(
+        &mut self,
+        text: &str,
+        position: Vector2,
+        scale: f32,
+        color: [f32; 4],
+        align: TextAlign,
+    ) {
+        let Some(font) = self.font else {
+            return;
+        };
+        for (texture, instance) in font.layout(text, position, scale, color, align) {
+            self.screen_batch
+                .push(self.screen_order, &texture, instance);
+        }
+    }
+
+    /// 画面座標で矩形を塗りつぶす（positionは左上）
+    pub fn draw_rect(&mut self, position: Vector2, size: Vector2, color: [f32; 4]) {
+        self.fill_rect(self.screen_order, position, size, color);
+    }
+
+    /// 指定した描画順で矩形を塗りつぶす
+    pub fn fill_rect(&mut self, order: i32, position: Vector2, size: Vector2, color: [f32; 4]) {
+        let Some(texture) = self.texture_manager.get_texture(WHITE_TEXTURE) else {
+            return;
+        };
+        let instance = SpriteInstance::new(
+            position + size * 0.5,
+            0.0,
+            Vector2::new(
+                size.x / texture.size.0 as f32,
+                size.y / texture.size.1 as f32,
+            ),
+            texture.size,
+        )
+        .with_color(color);
+        self.screen_batch.push(order, &texture, instance);
+    }
+
+    /// 画面座標でテクスチャを描く
+    pub fn draw_texture(&mut self, texture_name: &str, instance: SpriteInstance) {
+        if let Some(texture) = self.texture_manager.get_texture(texture_name) {
+            self.screen_batch
+                .push(self.screen_order, &texture, instance);
+        }
+    }
+
+    /// HUD（得点・残機・ウェーブとメッセージ）を描く
+    pub fn draw_hud(&mut self, hud: &Hud, world: &World) {
+        let Some(font) = self.font else {
+            return;
+        };
+        let life_icon = self.texture_manager.get_texture(super::ship::SHIP_TEXTURE);
+        for (texture, instance) in
+            hud.layout(&world.stats(), font, life_icon.as_ref(), self.screen_size)
+        {
+            self.screen_batch
+                .push(self.screen_order, &texture, instance);
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use super::bitmap_font::BitmapFont;
+use super::render_context::{RenderContext, WHITE_TEXTURE};
+use super::scene::SceneStack;
+use super::sprite_batch::SpriteBatch;
+use super::sprite_instance::{CameraUniform, SpriteInstance};
+use super::texture::TextureManager;
+use super::vector2::Vector2;
+use std::sync::Arc;
+use wgpu;
+use wgpu::util::DeviceExt;
+
+/// ウィンドウにシーンを描画する
+///
+/// wgpuのデバイスやサーフェス、テクスチャなど描画だけに必要なものをまとめる。
+/// ゲームの進行はこれが無くても（ヘッドレスでも）動く。
+pub struct Renderer {
+    pub texture_manager: TextureManager,
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    surface: wgpu::Surface,
+    surface_config: wgpu::SurfaceConfiguration,
+    render_pipeline: wgpu::RenderPipeline,
+    /// 正射影行列を保持するユニフォームバッファ
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+    /// HUD用のカメラ（常に画面座標をそのまま使う）
+    hud_camera_buffer: wgpu::Buffer,
+    hud_camera_bind_group: wgpu::BindGroup,
+    /// テクスチャごとにスプライトをまとめて描画するバッチ
+    sprite_batch: SpriteBatch,
+    /// ワールドの後に描くHUDと文字列のバッチ
+    hud_batch: SpriteBatch,
+    /// 文字列の描画に使うフォント（読み込めなければNone）
+    font: Option<BitmapFont>,
+}
+
+impl Renderer {
+    /// ウィンドウに描画する準備をし、テクスチャとフォントを読み込む
+    pub fn new(window: &winit::window::Window) -> Self {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let surface = unsafe { instance.create_surface(window) }.unwrap();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            compatible_surface: Some(&surface),
+        }))
+        .unwrap();
+
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .unwrap();
+
+        let device = Arc::new(device);
+        let queue = Arc::new(queue);
+        let texture_manager = TextureManager::new(device.clone(), queue.clone());
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_caps.formats[0],
+            width: window.inner_size().width,
+            height: window.inner_size().height,
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+        };
+        surface.configure(&device, &surface_config);
+
+        // カメラ（正射影行列）の設定
+        let camera_uniform =
+            CameraUniform::orthographic(surface_config.width as f32, surface_config.height as f32);
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Camera Buffer"),
+            contents: bytemuck::cast_slice(&[camera_uniform]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let hud_camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("HUD Camera Buffer"),
+            contents: bytemuck::cast_slice(&[camera_uniform]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let hud_camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("HUD Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: hud_camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let sprite_batch = SpriteBatch::new(&device);
+        let hud_batch = SpriteBatch::new(&device);
+
+        // シェーダーとパイプラインの設定
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Sprite Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(
+                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("Pipeline Layout"),
+                    bind_group_layouts: &[
+                        texture_manager.get_bind_group_layout(),
+                        &camera_bind_group_layout,
+                    ],
+                    push_constant_ranges: &[],
+                }),
+            ),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[SpriteInstance::desc()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let mut renderer = Self {
+            texture_manager,
+            device,
+            queue,
+            surface,
+            surface_config,
+            render_pipeline,
+            camera_buffer,
+            camera_bind_group,
+            hud_camera_buffer,
+            hud_camera_bind_group,
+            sprite_batch,
+            hud_batch,
+            font: None,
+        };
+
+        // テクスチャを事前にロード
+        println!("Current directory: {:?}", std::env::current_dir().unwrap());
+        renderer.texture_manager.load_texture("Ship.png");
+        renderer.texture_manager.load_texture("ShipWithThrust.png");
+        renderer.texture_manager.load_texture("Asteroid.png");
+        renderer.texture_manager.load_texture("Laser.png");
+        renderer
+            .texture_manager
+            .add_texture(WHITE_TEXTURE, (1, 1), &[255, 255, 255, 255]);
+        renderer.font = BitmapFont::load("Font.txt", &mut renderer.texture_manager);
+
+        renderer
+    }
+
+    /// ウィンドウサイズの変更に合わせてサーフェスと射影行列を更新
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.surface_config.width = width;
+        self.surface_config.height = height;
+        self.surface.configure(&self.device, &self.surface_config);
+
+        let camera_uniform = CameraUniform::orthographic(width as f32, height as f32);
+        self.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[camera_uniform]),
+        );
+        self.queue.write_buffer(
+            &self.hud_camera_buffer,
+            0,
+            bytemuck::cast_slice(&[camera_uniform]),
+        );
+    }
+
+    /// 画面の大きさ
+    pub fn screen_size(&self) -> Vector2 {
+        Vector2::new(
+            self.surface_config.width as f32,
+            self.surface_config.height as f32,
+        )
+    }
+
+    pub fn font(&self) -> Option<&BitmapFont> {
+        self.font.as_ref()
+    }
+
+    /// 見えているシーンを描画する（interpolationは前回の更新から補間する割合）
+    pub fn render(&mut self, scenes: &mut SceneStack, interpolation: f32) {
+        // 見えているシーンのスプライトと文字列を集める
+        let screen_size = self.screen_size();
+        self.sprite_batch.begin();
+        self.hud_batch.begin();
+        {
+            let mut ctx = RenderContext::new(
+                &mut self.sprite_batch,
+                &mut self.hud_batch,
+                &mut self.texture_manager,
+                self.font.as_ref(),
+                screen_size,
+            );
+            ctx.set_interpolation(interpolation);
+            scenes.render(&mut ctx);
+        }
+        self.sprite_batch.prepare(&self.device, &self.queue);
+        self.hud_batch.prepare(&self.device, &self.queue);
+
+        let output = self.surface.get_current_texture().unwrap();
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: 0.1,
+                            g: 0.2,
+                            b: 0.3,
+                            a: 1.0,
+                        }),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(1, &self.camera_bind_group, &[]);
+
+            // テクスチャごとに1回の描画コールでスプライトを描画
+            self.sprite_batch.draw(&mut render_pass);
+
+            render_pass.set_bind_group(1, &self.hud_camera_bind_group, &[]);
+            self.hud_batch.draw(&mut render_pass);
+        }
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+        output.present();
+    }
+}
@@ -0,0 +1,554 @@
+use super::high_score::HighScoreTable;
+use super::input_map::InputMap;
+use super::stable_hasher::StableHasher;
+use super::vector2::Vector2;
+use super::wave::WaveConfig;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, Write};
+use std::path::Path;
+use winit::event::VirtualKeyCode;
+
+/// 今のリプレイファイルの形式のバージョン
+pub const REPLAY_VERSION: u8 = 1;
+
+/// リプレイファイルの先頭に置く識別子
+const MAGIC: &[u8; 4] = b"A2RP";
+
+/// 記録できる更新の回数の上限（60Hzで6時間分）
+///
+/// 壊れたファイルや悪意のあるファイルで大量のメモリを確保しないように、読み込むときにも確かめる。
+pub const MAX_REPLAY_TICKS: usize = 60 * 60 * 60 * 6;
+
+/// 同じ入力が続く区間1つに最低限必要なバイト数（続く回数・キーのビット・押し始めたキーの数）
+const MIN_RUN_BYTES: usize = 3;
+
+/// 記録するキー（ファイルの中ではこの表の位置で表す）
+///
+/// ゲームで使う可能性のあるキーだけを記録する。ここに無いキーは記録されないので、
+/// 新しいキーを操作に割り当てるときはここにも足すこと（末尾に足せば古いファイルも読める）。
+const RECORDED_KEYS: [VirtualKeyCode; 49] = [
+    VirtualKeyCode::A,
+    VirtualKeyCode::B,
+    VirtualKeyCode::C,
+    VirtualKeyCode::D,
+    VirtualKeyCode::E,
+    VirtualKeyCode::F,
+    VirtualKeyCode::G,
+    VirtualKeyCode::H,
+    VirtualKeyCode::I,
+    VirtualKeyCode::J,
+    VirtualKeyCode::K,
+    VirtualKeyCode::L,
+    VirtualKeyCode::M,
+    VirtualKeyCode::N,
+    VirtualKeyCode::O,
+    VirtualKeyCode::P,
+    VirtualKeyCode::Q,
+    VirtualKeyCode::R,
+    VirtualKeyCode::S,
+    VirtualKeyCode::T,
+    VirtualKeyCode::U,
+    VirtualKeyCode::V,
+    VirtualKeyCode::W,
+    VirtualKeyCode::X,
+    VirtualKeyCode::Y,
+    VirtualKeyCode::Z,
+    VirtualKeyCode::Key0,
+    VirtualKeyCode::Key1,
+    VirtualKeyCode::Key2,
+    VirtualKeyCode::Key3,
+    VirtualKeyCode::Key4,
+    VirtualKeyCode::Key5,
+    VirtualKeyCode::Key6,
+    VirtualKeyCode::Key7,
+    VirtualKeyCode::Key8,
+    VirtualKeyCode::Key9,
+    VirtualKeyCode::Space,
+    VirtualKeyCode::Return,
+    VirtualKeyCode::Escape,
+    VirtualKeyCode::Back,
+    VirtualKeyCode::Tab,
+    VirtualKeyCode::Left,
+    VirtualKeyCode::Right,
+    VirtualKeyCode::Up,
+    VirtualKeyCode::Down,
+    VirtualKeyCode::LShift,
+    VirtualKeyCode::RShift,
+    VirtualKeyCode::LControl,
+    VirtualKeyCode::RControl,
+];
+
+/// 1回の更新で使われた入力
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayTick {
+    /// 押されていたキー
+    pub held: Vec<VirtualKeyCode>,
+    /// 前回の更新の後に押し始めたキー（押した順）
+    pub pressed: Vec<VirtualKeyCode>,
+}
+
+impl ReplayTick {
+    /// 押されていたキーを表の位置のビットで表す
+    fn held_mask(&self) -> u64 {
+        self.held
+            .iter()
+            .filter_map(|key| key_index(*key))
+            .fold(0, |mask, index| mask | 1 << index)
+    }
+}
+
+/// キーの表の位置
+fn key_index(key: VirtualKeyCode) -> Option<u8> {
+    RECORDED_KEYS
+        .iter()
+        .position(|&k| k == key)
+        .map(|index| index as u8)
+}
+
+/// 表の位置のキー
+fn key_at(index: u8) -> Result<VirtualKeyCode, String> {
+    RECORDED_KEYS
+        .get(index as usize)
+        .copied()
+        .ok_or_else(|| format!("unknown key index {}", index))
+}
+
+/// 入力の記録
+///
+/// シードと設定、記録を始めたときのハイスコア表、更新ごとの入力を持つ。
+/// 同じものから始めて同じ入力を与えれば、ゲームは同じように進む。
+///
+/// ファイルは次の順にリトルエンディアンで書く。
+///
+/// - `A2RP`、バージョン（1バイト）
+/// - シード（u64）、設定のハッシュ（u64）、画面の幅と高さ（f32）、1回の更新の時間（f32）
+/// - ハイスコア表（長さ付きのテキスト）
+/// - 更新の回数と、同じ入力が続く区間の数（可変長整数）
+/// - 区間ごとに、続く回数（可変長整数）、押されていたキーのビット（可変長整数）、
+///   押し始めたキーの数（1バイト）と表の位置（1バイトずつ）
+/// - 終了時のチェックサムがあるか（1バイト）とチェックサム（u64）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    seed: u64,
+    config_hash: u64,
+    world_size: Vector2,
+    step_time: f32,
+    /// 記録を始めたときのハイスコア表（ゲームオーバー後の画面の分岐に関わる）
+    high_scores: HighScoreTable,
+    ticks: Vec<ReplayTick>,
+    /// 記録を終えたときのゲームの状態のチェックサム
+    checksum: Option<u64>,
+}
+
+impl Replay {
+    pub fn new(
+        seed: u64,
+        config_hash: u64,
+        world_size: Vector2,
+        step_time: f32,
+        high_scores: HighScoreTable,
+    ) -> Self {
+        Self {
+            seed,
+            config_hash,
+            world_size,
+            step_time,
+            high_scores,
+            ticks: Vec::new(),
+            checksum: None,
+        }
+    }
+
+    /// ゲームの進行に関わる設定のハッシュ
+    pub fn config_hash(
+        world_size: Vector2,
+        step_time: f32,
+        wave_config: &WaveConfig,
+        input_map: &InputMap,
+    ) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_f32(world_size.x);
+        hasher.write_f32(world_size.y);
+        hasher.write_f32(step_time);
+        wave_config.write_hash(&mut hasher);
+        input_map.write_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn recorded_config_hash(&self) -> u64 {
+        self.config_hash
+    }
+
+    pub fn world_size(&self) -> Vector2 {
+        self.world_size
+    }
+
+    pub fn step_time(&self) -> f32 {
+        self.step_time
+    }
+
+    pub fn high_scores(&self) -> &HighScoreTable {
+        &self.high_scores
+    }
+
+    pub fn ticks(&self) -> &[ReplayTick] {
+        &self.ticks
+    }
+
+    /// 記録できる回数の上限に達したか
+    pub fn is_full(&self) -> bool {
+        self.ticks.len() >= MAX_REPLAY_TICKS
+    }
+
+    /// 1回の更新の入力を追加（記録しないキーは取り除く）
+    ///
+    /// 押されていたキーは表の順に並べ替える（操作は割り当ての順に決まるので順番は関係ない）。
+    /// 上限に達していれば何もしない。
+    pub fn push_tick(&mut self, held: &[VirtualKeyCode], pressed: &[VirtualKeyCode]) {
+        if self.is_full() {
+            return;
+        }
+        let held = RECORDED_KEYS
+            .iter()
+            .copied()
+            .filter(|key| held.contains(key))
+            .collect();
+        let pressed = pressed
+            .iter()
+            .copied()
+            .filter(|key| key_index(*key).is_some())
+            .take(u8::MAX as usize)
+            .collect();
+        self.ticks.push(ReplayTick { held, pressed });
+    }
+
+    pub fn checksum(&self) -> Option<u64> {
+        self.checksum
+    }
+
+    pub fn set_checksum(&mut self, checksum: u64) {
+        self.checksum = Some(checksum);
+    }
+
+    /// ファイルから読み込む
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        println!("Loading replay: {:?}", path);
+        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// ファイルに保存（一時ファイルに書いてから置き換える）
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        {
+            let mut file = fs::File::create(&temp_path)?;
+            file.write_all(&self.to_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&temp_path, path)
+    }
+
+    /// ファイルの形式に変換
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(REPLAY_VERSION);
+        out.extend_from_slice(&self.seed.to_le_bytes());
+        out.extend_from_slice(&self.config_hash.to_le_bytes());
+        out.extend_from_slice(&self.world_size.x.to_le_bytes());
+        out.extend_from_slice(&self.world_size.y.to_le_bytes());
+        out.extend_from_slice(&self.step_time.to_le_bytes());
+        let high_scores = self.high_scores.to_text();
+        write_varint(&mut out, high_scores.len() as u64);
+        out.extend_from_slice(high_scores.as_bytes());
+
+        // 同じ入力が続く区間にまとめる
+        let mut runs: Vec<(u64, &ReplayTick)> = Vec::new();
+        for tick in &self.ticks {
+            match runs.last_mut() {
+                Some((count, last)) if *last == tick => *count += 1,
+                _ => runs.push((1, tick)),
+            }
+        }
+        write_varint(&mut out, self.ticks.len() as u64);
+        write_varint(&mut out, runs.len() as u64);
+        for (count, tick) in runs {
+            write_varint(&mut out, count);
+            write_varint(&mut out, tick.held_mask());
+            let pressed: Vec<u8> = tick.pressed.iter().filter_map(|k| key_index(*k)).collect();
+            out.push(pressed.len() as u8);
+            out.extend_from_slice(&pressed);
+        }
+
+        match self.checksum {
+            Some(checksum) => {
+                out.push(1);
+                out.extend_from_slice(&checksum.to_le_bytes());
+            }
+            None => out.push(0),
+        }
+        out
+    }
+
+    /// ファイルの内容を解析
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { bytes, position: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err("not a replay file".to_string());
+        }
+        let version = reader.u8()?;
+        if version != REPLAY_VERSION {
+            return Err(format!("unsupported replay version {}", version));
+        }
+        let seed = reader.u64()?;
+        let config_hash = reader.u64()?;
+        let world_size = Vector2::new(reader.f32()?, reader.f32()?);
+        let step_time = reader.f32()?;
+        let positive = |value: f32| value.is_finite() && value > 0.0;
+        if !positive(world_size.x) || !positive(world_size.y) || !positive(step_time) {
+            return Err("bad world size or step time".to_string());
+        }
+        let length = reader.varint()? as usize;
+        let text = std::str::from_utf8(reader.take(length)?).map_err(|e| e.to_string())?;
+        let high_scores = HighScoreTable::parse(text)?;
+
+        let tick_count = reader.varint()?;
+        if tick_count > MAX_REPLAY_TICKS as u64 {
+            return Err(format!("too many ticks: {}", tick_count));
+        }
+        let tick_count = tick_count as usize;
+        let run_count = reader.varint()?;
+        if run_count > (reader.remaining() / MIN_RUN_BYTES) as u64 {
+            return Err(format!("too many runs for the file size: {}", run_count));
+        }
+        let mut ticks = Vec::new();
+        for _ in 0..run_count {
+            let count = reader.varint()?;
+            let mask = reader.varint()?;
+            let held = (0..64u8)
+                .filter(|index| mask & (1 << index) != 0)
+                .map(key_at)
+                .collect::<Result<Vec<_>, _>>()?;
+            let pressed_count = reader.u8()? as usize;
+            let pressed = reader
+                .take(pressed_count)?
+                .iter()
+                .map(|&index| key_at(index))
+                .collect::<Result<Vec<_>, _>>()?;
+            let count = usize::try_from(count)
+                .ok()
+                .filter(|&count| {
+                    ticks
+                        .len()
+                        .checked_add(count)
+                        .is_some_and(|total| total <= tick_count)
+                })
+                .ok_or_else(|| "more ticks than declared".to_string())?;
+            let tick = ReplayTick { held, pressed };
+            ticks.resize(ticks.len() + count, tick);
+        }
+        if ticks.len() != tick_count {
+            return Err(format!(
+                "expected {} ticks, found {}",
+                tick_count,
+                ticks.len()
+            ));
+        }
+
+        let checksum = match reader.u8()? {
+            0 => None,
+            _ => Some(reader.u64()?),
+        };
+        Ok(Self {
+            seed,
+            config_hash,
+            world_size,
+            step_time,
+            high_scores,
+            ticks,
+            checksum,
+        })
+    }
+}
+
+/// 7ビットずつ書く可変長整数
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// バイト列を先頭から読む
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// まだ読んでいないバイト数
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| "unexpected end of file".to_string())?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("invalid variable-length integer".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_replay() -> Replay {
+        let mut replay = Replay::new(
+            42,
+            7,
+            Vector2::new(800.0, 600.0),
+            1.0 / 60.0,
+            HighScoreTable::new(),
+        );
+        replay.push_tick(&[VirtualKeyCode::Return], &[VirtualKeyCode::Return]);
+        for _ in 0..100 {
+            replay.push_tick(&[VirtualKeyCode::Space, VirtualKeyCode::D], &[]);
+        }
+        replay.push_tick(&[], &[VirtualKeyCode::K, VirtualKeyCode::Key9]);
+        replay.set_checksum(0x1234_5678_9abc_def0);
+        replay
+    }
+
+    /// 入力が無く、チェックサムも無いリプレイのファイルから、更新の回数の前までを取り出す
+    fn header() -> Vec<u8> {
+        let replay = Replay::new(
+            1,
+            2,
+            Vector2::new(800.0, 600.0),
+            1.0 / 60.0,
+            HighScoreTable::new(),
+        );
+        let mut bytes = replay.to_bytes();
+        // 更新の回数（0）、区間の数（0）、チェックサムの有無（0）
+        bytes.truncate(bytes.len() - 3);
+        bytes
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let replay = sample_replay();
+        let bytes = replay.to_bytes();
+        assert_eq!(Replay::from_bytes(&bytes).unwrap(), replay);
+        // 同じ入力が続く区間はまとめて書く
+        assert!(bytes.len() < 100);
+    }
+
+    #[test]
+    fn unrecorded_keys_are_dropped() {
+        let mut replay = sample_replay();
+        replay.push_tick(
+            &[VirtualKeyCode::F5, VirtualKeyCode::W],
+            &[VirtualKeyCode::F5],
+        );
+        let tick = replay.ticks().last().unwrap();
+        assert_eq!(tick.held, vec![VirtualKeyCode::W]);
+        assert!(tick.pressed.is_empty());
+    }
+
+    #[test]
+    fn truncated_files_are_rejected() {
+        let bytes = sample_replay().to_bytes();
+        for length in 0..bytes.len() {
+            assert!(Replay::from_bytes(&bytes[..length]).is_err());
+        }
+    }
+
+    #[test]
+    fn huge_tick_count_is_rejected() {
+        let mut bytes = header();
+        write_varint(&mut bytes, u64::MAX);
+        write_varint(&mut bytes, 1);
+        assert!(Replay::from_bytes(&bytes).is_err());
+
+        let mut bytes = header();
+        write_varint(&mut bytes, MAX_REPLAY_TICKS as u64 + 1);
+        write_varint(&mut bytes, 0);
+        bytes.push(0);
+        assert!(Replay::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn huge_run_is_rejected() {
+        let mut bytes = header();
+        write_varint(&mut bytes, 10);
+        write_varint(&mut bytes, 2);
+        for count in [5, u64::MAX] {
+            write_varint(&mut bytes, count);
+            write_varint(&mut bytes, 0);
+            bytes.push(0);
+        }
+        bytes.push(0);
+        assert!(Replay::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn huge_run_count_is_rejected() {
+        let mut bytes = header();
+        write_varint(&mut bytes, 10);
+        write_varint(&mut bytes, u64::MAX);
+        bytes.extend_from_slice(&[0; 16]);
+        assert!(Replay::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn bad_step_time_is_rejected() {
+        let mut replay = sample_replay();
+        replay.step_time = f32::NAN;
+        assert!(Replay::from_bytes(&replay.to_bytes()).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use super::component::{Component, ComponentContext};
+use super::vector2::Vector2;
+
+/// 力を加えて動かす剛体
+///
+/// 毎フレーム、加えられた力と質量から加速度を求めて速度に積分し、
+/// 抵抗で減速させてから位置と回転を更新する。加えた力はフレームごとに消える。
+pub struct RigidBodyComponent {
+    velocity: Vector2,
+    acceleration: Vector2,
+    angular_velocity: f32,
+    /// このフレームに加えられた力
+    force: Vector2,
+    /// このフレームに加えられた回転力
+    torque: f32,
+    mass: f32,
+    /// 1秒あたりの速度の減衰率
+    linear_drag: f32,
+    /// 1秒あたりの角速度の減衰率
+    angular_drag: f32,
+    max_speed: f32,
+}
+
+impl RigidBodyComponent {
+    pub fn new(mass: f32, linear_drag: f32, angular_drag: f32, max_speed: f32) -> Self {
+        Self {
+            velocity: Vector2::zero(),
+            acceleration: Vector2::zero(),
+            angular_velocity: 0.0,
+            force: Vector2::zero(),
+            torque: 0.0,
+            mass: mass.max(f32::EPSILON),
+            linear_drag,
+            angular_drag,
+            max_speed,
+        }
+    }
+
+    /// このフレームに力を加える
+    pub fn add_force(&mut self, force: Vector2) {
+        self.force += force;
+    }
+
+    /// このフレームに回転力を加える
+    pub fn add_torque(&mut self, torque: f32) {
+        self.torque += torque;
+    }
+
+    pub fn velocity(&self) -> Vector2 {
+        self.velocity
+    }
+
+    pub fn set_velocity(&mut self, velocity: Vector2) {
+        self.velocity = velocity;
+    }
+
+    /// 直前のフレームの加速度
+    pub fn acceleration(&self) -> Vector2 {
+        self.acceleration
+    }
+
+    pub fn angular_velocity(&self) -> f32 {
+        self.angular_velocity
+    }
+
+    pub fn set_angular_velocity(&mut self, angular_velocity: f32) {
+        self.angular_velocity = angular_velocity;
+    }
+
+    pub fn mass(&self) -> f32 {
+        self.mass
+    }
+
+    pub fn set_mass(&mut self, mass: f32) {
+        self.mass = mass.max(f32::EPSILON);
+    }
+
+    pub fn linear_drag(&self) -> f32 {
+        self.linear_drag
+    }
+
+    pub fn set_linear_drag(&mut self, drag: f32) {
+        self.linear_drag = drag;
+    }
+
+    pub fn angular_drag(&self) -> f32 {
+        self.angular_drag
+    }
+
+    pub fn set_angular_drag(&mut self, drag: f32) {
+        self.angular_drag = drag;
+    }
+
+    pub fn max_speed(&self) -> f32 {
+        self.max_speed
+    }
+
+    pub fn set_max_speed(&mut self, speed: f32) {
+        self.max_speed = speed;
+    }
+}
+
+impl Component for RigidBodyComponent {
+    fn update(&mut self, ctx: &mut ComponentContext, delta_time: f32) {
+        // 力から加速度を求めて速度に積分する
+        self.acceleration = self.force / self.mass;
+        self.velocity += self.acceleration * delta_time;
+        self.angular_velocity += self.torque / self.mass * delta_time;
+        self.force = Vector2::zero();
+        self.torque = 0.0;
+
+        // 抵抗で減速（delta_timeが大きくても逆向きにならない形にする）
+        self.velocity /= 1.0 + self.linear_drag * delta_time;
+        self.angular_velocity /= 1.0 + self.angular_drag * delta_time;
+
+        let speed = self.velocity.length();
+        if speed > self.max_speed {
+            self.velocity *= self.max_speed / speed;
+        }
+
+        let actor = ctx.owner_mut();
+        let rot = actor.rotation();
+        actor.set_rotation(rot + self.angular_velocity * delta_time);
+        let pos = actor.position();
+        actor.set_position(pos + self.velocity * delta_time);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
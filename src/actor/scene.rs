@@ -0,0 +1,364 @@
+use super::high_score::{HighScoreEntry, HighScoreTable};
+use super::input_map::Action;
+use super::random::Random;
+use super::render_context::RenderContext;
+use super::sprite_component::{DRAW_ORDER_FADE, DRAW_ORDER_HUD};
+use super::stable_hasher::StableHasher;
+use super::vector2::Vector2;
+use super::wave::WaveConfig;
+use super::world::World;
+use std::hash::Hasher;
+use std::path::PathBuf;
+use winit::event::VirtualKeyCode;
+
+/// 画面を切り替えるときのフェードアウト・フェードインそれぞれの時間（秒）
+pub const FADE_TIME: f32 = 0.3;
+
+/// シーンが返す画面の切り替え
+pub enum SceneTransition {
+    /// 切り替えない
+    None,
+    /// 今のシーンの上に新しいシーンを積む
+    Push(Box<dyn Scene>),
+    /// 今のシーンを取り除いて下のシーンに戻る
+    Pop,
+    /// 今のシーンを新しいシーンと入れ替える（フェードする）
+    Replace(Box<dyn Scene>),
+    /// 全てのシーンを取り除いて新しいシーンにする（フェードする）
+    ReplaceAll(Box<dyn Scene>),
+    /// ゲームを終了する
+    Quit,
+}
+
+/// 1フレーム分の入力
+#[derive(Debug, Default, Clone)]
+pub struct SceneInput {
+    /// 押されている操作
+    pub actions: Vec<Action>,
+    /// このフレームに押し始めた操作
+    pub pressed_actions: Vec<Action>,
+    /// このフレームに押し始めたキー（文字入力に使う）
+    pub pressed_keys: Vec<VirtualKeyCode>,
+}
+
+impl SceneInput {
+    /// このフレームに押し始めた操作か
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.pressed_actions.contains(&action)
+    }
+}
+
+/// シーンから使えるゲーム全体の状態
+pub struct SceneContext {
+    /// 画面（ゲーム空間）の大きさ
+    world_size: Vector2,
+    /// プレイ中の小惑星のウェーブの設定
+    wave_config: WaveConfig,
+    /// ハイスコア表
+    high_scores: HighScoreTable,
+    /// ハイスコア表の保存先（データディレクトリが分からなければNone）
+    high_score_path: Option<PathBuf>,
+    /// 新しく作るワールドのシードを決めるランダム値生成
+    random: Random,
+}
+
+impl SceneContext {
+    pub fn new(
+        world_size: Vector2,
+        wave_config: WaveConfig,
+        high_scores: HighScoreTable,
+        high_score_path: Option<PathBuf>,
+        seed: u64,
+    ) -> Self {
+        Self {
+            world_size,
+            wave_config,
+            high_scores,
+            high_score_path,
+            random: Random::new(seed),
+        }
+    }
+
+    /// ゲーム全体のシード
+    pub fn seed(&self) -> u64 {
+        self.random.seed()
+    }
+
+    /// 新しく作るワールドのシード（ゲーム全体のシードから順に決まる）
+    pub fn next_seed(&mut self) -> u64 {
+        self.random.next_u64()
+    }
+
+    pub fn world_size(&self) -> Vector2 {
+        self.world_size
+    }
+
+    pub fn set_world_size(&mut self, world_size: Vector2) {
+        self.world_size = world_size;
+    }
+
+    pub fn wave_config(&self) -> &WaveConfig {
+        &self.wave_config
+    }
+
+    pub fn high_scores(&self) -> &HighScoreTable {
+        &self.high_scores
+    }
+
+    /// ハイスコア表に記録して保存し、載った順位（0から数える）を返す
+    pub fn submit_high_score(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self.high_scores.insert(entry)?;
+        println!("New high score: rank {}", rank + 1);
+        if let Some(path) = &self.high_score_path {
+            if let Err(e) = self.high_scores.save(path) {
+                println!("Failed to save high scores: {:?} - Error: {}", path, e);
+            }
+        }
+        Some(rank)
+    }
+}
+
+/// タイトル・プレイ中・一時停止などの画面1つ分
+///
+/// シーンはそれぞれ自分のWorld（アクター）と入力処理、描画を持つ。
+pub trait Scene {
+    /// デバッグ表示用の名前
+    fn name(&self) -> &str;
+
+    /// 入力を処理する（一番上のシーンにだけ届く）
+    fn process_input(&mut self, _ctx: &mut SceneContext, _input: &SceneInput) -> SceneTransition {
+        SceneTransition::None
+    }
+
+    /// 1フレーム分進める（一番上のシーンだけが進む）
+    fn update(&mut self, _ctx: &mut SceneContext, _delta_time: f32) -> SceneTransition {
+        SceneTransition::None
+    }
+
+    /// 描画する
+    fn render(&mut self, ctx: &mut RenderContext);
+
+    /// 下のシーンの上に重ねて描くか（一時停止画面など）
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// 画面の大きさが変わった
+    fn resize(&mut self, _world_size: Vector2) {}
+
+    /// シーンが持っているワールド（持っていなければNone）
+    fn world(&self) -> Option<&World> {
+        None
+    }
+
+    /// リプレイの照合に使うシーンの状態をハッシュに加える（標準ではワールドの状態）
+    fn write_checksum(&self, hasher: &mut StableHasher) {
+        if let Some(world) = self.world() {
+            hasher.write_u64(world.checksum());
+        }
+    }
+}
+
+/// フェードの進行状況
+enum Fade {
+    /// フェードしていない
+    None,
+    /// 暗くしている途中（終わったら切り替えを適用する）
+    Out(f32, SceneTransition),
+    /// 明るくしている途中
+    In(f32),
+}
+
+/// シーンを積み重ねて管理する
+///
+/// 入力と更新は一番上のシーンにだけ届き、描画は一番上から下へ
+/// 重ねて描くシーン（is_overlay）をたどった先のシーンから行う。
+/// PushとPopはすぐに、ReplaceとReplaceAllはフェードアウトしてから切り替える。
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    fade: Fade,
+    /// Quitが要求されたか
+    quit: bool,
+}
+
+impl Default for SceneStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SceneStack {
+    pub fn new() -> Self {
+        Self {
+            scenes: Vec::new(),
+            fade: Fade::None,
+            quit: false,
+        }
+    }
+
+    /// シーンをすぐに積む
+    pub fn push(&mut self, scene: Box<dyn Scene>) {
+        self.scenes.push(scene);
+    }
+
+    /// 一番上のシーン
+    pub fn top(&self) -> Option<&dyn Scene> {
+        self.scenes.last().map(|scene| scene.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// 上から見て最初にワールドを持っているシーンのワールド
+    pub fn world(&self) -> Option<&World> {
+        self.scenes.iter().rev().find_map(|scene| scene.world())
+    }
+
+    /// 全てのシーンの名前と状態から作るチェックサム
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        for scene in &self.scenes {
+            hasher.write_str(scene.name());
+            scene.write_checksum(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// ゲームの終了が要求されたか（シーンが全て無くなった場合も含む）
+    pub fn is_quit(&self) -> bool {
+        self.quit || self.scenes.is_empty()
+    }
+
+    /// 画面を覆う黒の不透明度（0.0〜1.0）
+    pub fn fade_alpha(&self) -> f32 {
+        match &self.fade {
+            Fade::None => 0.0,
+            Fade::Out(time, _) => (time / FADE_TIME).clamp(0.0, 1.0),
+            Fade::In(time) => (1.0 - time / FADE_TIME).clamp(0.0, 1.0),
+        }
+    }
+
+    /// 一番上のシーンに入力を渡して進め、フェードを進める
+    pub fn update(&mut self, ctx: &mut SceneContext, input: &SceneInput, delta_time: f32) {
+        self.update_fade(delta_time);
+
+        // フェード中は入力を受け付けず、シーンも止めておく
+        if !matches!(self.fade, Fade::None) {
+            return;
+        }
+        let Some(scene) = self.scenes.last_mut() else {
+            return;
+        };
+        let transition = scene.process_input(ctx, input);
+        self.apply(transition);
+
+        if !matches!(self.fade, Fade::None) {
+            return;
+        }
+        let Some(scene) = self.scenes.last_mut() else {
+            return;
+        };
+        let transition = scene.update(ctx, delta_time);
+        self.apply(transition);
+    }
+
+    fn update_fade(&mut self, delta_time: f32) {
+        match std::mem::replace(&mut self.fade, Fade::None) {
+            Fade::None => {}
+            Fade::Out(time, transition) => {
+                let time = time + delta_time;
+                if time < FADE_TIME {
+                    self.fade = Fade::Out(time, transition);
+                } else {
+                    self.apply_now(transition);
+                    self.fade = Fade::In(0.0);
+                }
+            }
+            Fade::In(time) => {
+                let time = time + delta_time;
+                if time < FADE_TIME {
+                    self.fade = Fade::In(time);
+                }
+            }
+        }
+    }
+
+    /// 切り替えを適用する（ReplaceとReplaceAllはフェードアウトを始める）
+    pub fn apply(&mut self, transition: SceneTransition) {
+        match transition {
+            SceneTransition::None => {}
+            SceneTransition::Replace(_) | SceneTransition::ReplaceAll(_) => {
+                self.fade = Fade::Out(0.0, transition);
+            }
+            _ => self.apply_now(transition),
+        }
+    }
+
+    fn apply_now(&mut self, transition: SceneTransition) {
+        match transition {
+            SceneTransition::None => {}
+            SceneTransition::Push(scene) => self.push(scene),
+            SceneTransition::Pop => {
+                self.scenes.pop();
+            }
+            SceneTransition::Replace(scene) => {
+                self.scenes.pop();
+                self.push(scene);
+            }
+            SceneTransition::ReplaceAll(scene) => {
+                self.scenes.clear();
+                self.push(scene);
+            }
+            SceneTransition::Quit => self.quit = true,
+        }
+    }
+
+    /// 見えているシーンを下から順に描き、フェードを重ねる
+    pub fn render(&mut self, ctx: &mut RenderContext) {
+        // 上から見て最初の重ねないシーンより下は隠れている
+        let first_visible = self
+            .scenes
+            .iter()
+            .rposition(|scene| !scene.is_overlay())
+            .unwrap_or(0);
+        // 止まっているシーンを補間すると前回の更新との間で揺れるので、
+        // 補間するのは進んでいる一番上のシーンだけにする
+        let interpolation = ctx.interpolation();
+        let top = self.scenes.len().saturating_sub(1);
+        let fading = !matches!(self.fade, Fade::None);
+        for (index, scene) in self.scenes.iter_mut().enumerate().skip(first_visible) {
+            // 上のシーンの文字や矩形ほど手前に描く
+            ctx.set_screen_order(DRAW_ORDER_HUD + index as i32 * 10);
+            ctx.set_interpolation(if index == top && !fading {
+                interpolation
+            } else {
+                1.0
+            });
+            scene.render(ctx);
+        }
+        ctx.set_interpolation(interpolation);
+
+        let alpha = self.fade_alpha();
+        if alpha > 0.0 {
+            let screen_size = ctx.screen_size();
+            ctx.fill_rect(
+                DRAW_ORDER_FADE,
+                Vector2::zero(),
+                screen_size,
+                [0.0, 0.0, 0.0, alpha],
+            );
+        }
+    }
+
+    /// 全てのシーンに画面の大きさの変更を伝える
+    pub fn resize(&mut self, world_size: Vector2) {
+        for scene in &mut self.scenes {
+            scene.resize(world_size);
+        }
+    }
+}
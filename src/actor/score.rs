@@ -0,0 +1,128 @@
+use super::asteroid::AsteroidSize;
+
+/// 敵の種類
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnemyKind {
+    /// 大きいUFO
+    LargeSaucer,
+    /// 小さいUFO
+    SmallSaucer,
+}
+
+/// 得点の対象
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ScoreSource {
+    /// 小惑星を壊した
+    Asteroid(AsteroidSize),
+    /// 敵を倒した
+    Enemy(EnemyKind),
+}
+
+/// 得点の設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreConfig {
+    large_asteroid: u32,
+    medium_asteroid: u32,
+    small_asteroid: u32,
+    large_saucer: u32,
+    small_saucer: u32,
+    /// この得点ごとに残機が1つ増える（0なら増えない）
+    extra_life_every: u32,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            large_asteroid: AsteroidSize::Large.score(),
+            medium_asteroid: AsteroidSize::Medium.score(),
+            small_asteroid: AsteroidSize::Small.score(),
+            large_saucer: 200,
+            small_saucer: 1000,
+            extra_life_every: 10000,
+        }
+    }
+}
+
+impl ScoreConfig {
+    /// 対象ごとの得点
+    pub fn points(&self, source: ScoreSource) -> u32 {
+        match source {
+            ScoreSource::Asteroid(AsteroidSize::Large) => self.large_asteroid,
+            ScoreSource::Asteroid(AsteroidSize::Medium) => self.medium_asteroid,
+            ScoreSource::Asteroid(AsteroidSize::Small) => self.small_asteroid,
+            ScoreSource::Enemy(EnemyKind::LargeSaucer) => self.large_saucer,
+            ScoreSource::Enemy(EnemyKind::SmallSaucer) => self.small_saucer,
+        }
+    }
+
+    /// 対象の得点を変更
+    pub fn set_points(&mut self, source: ScoreSource, points: u32) {
+        let field = match source {
+            ScoreSource::Asteroid(AsteroidSize::Large) => &mut self.large_asteroid,
+            ScoreSource::Asteroid(AsteroidSize::Medium) => &mut self.medium_asteroid,
+            ScoreSource::Asteroid(AsteroidSize::Small) => &mut self.small_asteroid,
+            ScoreSource::Enemy(EnemyKind::LargeSaucer) => &mut self.large_saucer,
+            ScoreSource::Enemy(EnemyKind::SmallSaucer) => &mut self.small_saucer,
+        };
+        *field = points;
+    }
+
+    pub fn extra_life_every(&self) -> u32 {
+        self.extra_life_every
+    }
+
+    pub fn set_extra_life_every(&mut self, points: u32) {
+        self.extra_life_every = points;
+    }
+}
+
+/// 得点と、残機が増える得点の進行を管理する
+pub struct Score {
+    config: ScoreConfig,
+    score: u32,
+    /// 次に残機が増える得点（増えない設定ならNone）
+    next_extra_life: Option<u32>,
+}
+
+impl Score {
+    pub fn new(config: ScoreConfig) -> Self {
+        let next_extra_life = (config.extra_life_every > 0).then_some(config.extra_life_every);
+        Self {
+            config,
+            score: 0,
+            next_extra_life,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// 次に残機が増える得点
+    pub fn next_extra_life(&self) -> Option<u32> {
+        self.next_extra_life
+    }
+
+    pub fn config(&self) -> &ScoreConfig {
+        &self.config
+    }
+
+    /// 得点を加え、増える残機の数を返す
+    pub fn award(&mut self, source: ScoreSource) -> u32 {
+        self.add(self.config.points(source))
+    }
+
+    /// 指定した点数を加え、増える残機の数を返す
+    pub fn add(&mut self, points: u32) -> u32 {
+        self.score = self.score.saturating_add(points);
+        let mut extra_lives = 0;
+        while let Some(threshold) = self.next_extra_life {
+            if self.score < threshold {
+                break;
+            }
+            extra_lives += 1;
+            self.next_extra_life = threshold.checked_add(self.config.extra_life_every);
+        }
+        extra_lives
+    }
+}
@@ -1,31 +1,57 @@
 @group(0) @binding(0) var t_diffuse: texture_2d<f32>;
 @group(0) @binding(1) var s_diffuse: sampler;
 
+struct Camera {
+    view_proj: mat4x4<f32>,
+};
+@group(1) @binding(0) var<uniform> camera: Camera;
+
+// スプライト1つ分のトランスフォームと見た目
+struct InstanceInput {
+    @location(0) position: vec2<f32>,
+    @location(1) size: vec2<f32>,
+    @location(2) rotation: f32,
+    @location(3) uv_offset: vec2<f32>,
+    @location(4) uv_size: vec2<f32>,
+    @location(5) color: vec4<f32>,
+};
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) tex_coords: vec2<f32>,
+    @location(1) color: vec4<f32>,
 };
 
 @vertex
-fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
+fn vs_main(@builtin(vertex_index) in_vertex_index: u32, instance: InstanceInput) -> VertexOutput {
     var out: VertexOutput;
     var pos: vec2<f32>;
-    
+
+    // 画面座標系はY軸下向きなので、-0.5が上端になる
     switch(in_vertex_index) {
-        case 0u: { pos = vec2<f32>(-0.5, -0.5); }  // 左下
-        case 1u: { pos = vec2<f32>(0.5, -0.5); }   // 右下
-        case 2u: { pos = vec2<f32>(-0.5, 0.5); }   // 左上
-        case 3u: { pos = vec2<f32>(-0.5, 0.5); }   // 左上
-        case 4u: { pos = vec2<f32>(0.5, -0.5); }   // 右下
-        default: { pos = vec2<f32>(0.5, 0.5); }    // 右上
+        case 0u: { pos = vec2<f32>(-0.5, 0.5); }   // 左下
+        case 1u: { pos = vec2<f32>(0.5, 0.5); }    // 右下
+        case 2u: { pos = vec2<f32>(-0.5, -0.5); }  // 左上
+        case 3u: { pos = vec2<f32>(-0.5, -0.5); }  // 左上
+        case 4u: { pos = vec2<f32>(0.5, 0.5); }    // 右下
+        default: { pos = vec2<f32>(0.5, -0.5); }   // 右上
     }
-    
-    out.clip_position = vec4<f32>(pos.x, pos.y, 0.0, 1.0);
-    out.tex_coords = vec2<f32>(pos.x + 0.5, 1.0 - (pos.y + 0.5));
+
+    // スケール → 回転 → 平行移動
+    let local = pos * instance.size;
+    let c = cos(instance.rotation);
+    let s = sin(instance.rotation);
+    let rotated = vec2<f32>(local.x * c - local.y * s, local.x * s + local.y * c);
+    let world = rotated + instance.position;
+
+    out.clip_position = camera.view_proj * vec4<f32>(world, 0.0, 1.0);
+    // テクスチャ内の描画範囲（フォントのグリフなど）に合わせる
+    out.tex_coords = instance.uv_offset + (pos + vec2<f32>(0.5, 0.5)) * instance.uv_size;
+    out.color = instance.color;
     return out;
 }
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(t_diffuse, s_diffuse, in.tex_coords);
-} 
\ No newline at end of file
+    return textureSample(t_diffuse, s_diffuse, in.tex_coords) * in.color;
+}
@@ -1,69 +1,130 @@
-use super::actor::Actor;
-use super::component::{Component, ComponentBase};
-use super::game::Game;
-use super::laser::Laser;
+use super::actor::ActorStatus;
+use super::asteroid::Asteroid;
+use super::circle_component::CircleComponent;
+use super::collision::CollisionEvent;
+use super::component::{Component, ComponentContext};
+use super::input_component::InputComponent;
+use super::input_map::Action;
+use super::rigid_body_component::RigidBodyComponent;
 use super::sprite_component::SpriteComponent;
-use super::texture::TextureManager;
 use super::vector2::Vector2;
-use std::f32::consts::PI;
 
+/// 通常時の宇宙船のテクスチャ
+pub const SHIP_TEXTURE: &str = "Ship.png";
+/// 推進中の宇宙船のテクスチャ
+pub const SHIP_THRUST_TEXTURE: &str = "ShipWithThrust.png";
+
+/// プレイヤーの宇宙船
+///
+/// スプライトや移動はWorld::setup_player_actorで同じアクターに付けたコンポーネントが担う。
 pub struct Ship {
-    base: ComponentBase,
     laser_cooldown: f32,
     laser_cooldown_timer: f32,
+    /// 無敵でいる残り時間
+    invulnerable_timer: f32,
+}
+
+/// 無敵中に点滅する間隔
+const BLINK_INTERVAL: f32 = 0.1;
+
+impl Default for Ship {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Ship {
-    pub fn new(texture_manager: &mut TextureManager) -> Self {
-        let mut ship = Self {
-            base: ComponentBase::new(),
+    pub fn new() -> Self {
+        Self {
             laser_cooldown: 0.5, // レーザーの発射間隔
             laser_cooldown_timer: 0.0,
-        };
+            invulnerable_timer: 0.0,
+        }
+    }
 
-        // スプライトコンポーネントを追加
-        let sprite = SpriteComponent::new("Ship.png", 100, texture_manager);
-        ship.add_component(Box::new(sprite));
+    /// 指定した時間だけ無敵にする（その間はスプライトが点滅する）
+    pub fn set_invulnerable(&mut self, duration: f32) {
+        self.invulnerable_timer = duration;
+    }
 
-        ship
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0.0
     }
 
-    pub fn shoot_laser(&mut self, game: &mut Game) {
-        if self.laser_cooldown_timer <= 0.0 {
-            if let Some(actor) = self.owner_mut() {
-                let pos = actor.position();
-                let rot = actor.rotation();
-                game.create_laser(pos, rot);
-                self.laser_cooldown_timer = self.laser_cooldown;
-            }
+    /// 宇宙船の先端から、所有者の向きでレーザーを発射
+    ///
+    /// レーザーは宇宙船の速度を引き継ぐ。
+    /// レーザーの生成は予約され、このフレームの更新が終わった後に行われる。
+    pub fn shoot_laser(&mut self, ctx: &mut ComponentContext) {
+        if self.laser_cooldown_timer > 0.0 {
+            return;
         }
+
+        // 先端は衝突判定の円のforward方向の端
+        let nose_distance = ctx
+            .get_component::<CircleComponent>()
+            .map_or(0.0, |circle| circle.radius());
+        let velocity = ctx
+            .get_component::<RigidBodyComponent>()
+            .map_or(Vector2::zero(), |body| body.velocity());
+        let rot = ctx.owner().rotation();
+        let pos = ctx.owner().position() + ctx.owner().forward() * nose_distance;
+        ctx.commands().spawn(move |world| {
+            world.create_laser(pos, rot, velocity);
+        });
+        self.laser_cooldown_timer = self.laser_cooldown;
     }
 
-    pub fn add_component(&mut self, component: Box<dyn Component>) {
-        if let Some(actor) = self.owner_mut() {
-            actor.add_component(component);
-        }
+    pub fn laser_cooldown(&self) -> f32 {
+        self.laser_cooldown
+    }
+
+    pub fn set_laser_cooldown(&mut self, cooldown: f32) {
+        self.laser_cooldown = cooldown;
     }
 }
 
 impl Component for Ship {
-    fn update(&mut self, delta_time: f32) {
+    fn update(&mut self, ctx: &mut ComponentContext, delta_time: f32) {
         // クールダウンタイマーを更新
         if self.laser_cooldown_timer > 0.0 {
             self.laser_cooldown_timer -= delta_time;
         }
-    }
 
-    fn set_owner(&mut self, owner: &mut Actor) {
-        self.base.set_owner(owner);
+        if self.invulnerable_timer > 0.0 {
+            self.invulnerable_timer -= delta_time;
+        }
+
+        // 推進中は噴射付きのスプライトに切り替える
+        let thrusting = ctx
+            .get_component::<InputComponent>()
+            .is_some_and(|input| input.is_thrusting());
+        let visible =
+            !self.is_invulnerable() || (self.invulnerable_timer / BLINK_INTERVAL) as i32 % 2 == 0;
+        if let Some(sprite) = ctx.get_component_mut::<SpriteComponent>() {
+            sprite.set_visible(visible);
+            sprite.request_texture(if thrusting {
+                SHIP_THRUST_TEXTURE
+            } else {
+                SHIP_TEXTURE
+            });
+        }
     }
 
-    fn owner(&self) -> Option<&Actor> {
-        self.base.owner()
+    fn process_input(&mut self, ctx: &mut ComponentContext, actions: &[Action]) {
+        if actions.contains(&Action::Fire) {
+            self.shoot_laser(ctx);
+        }
     }
 
-    fn owner_mut(&mut self) -> Option<&mut Actor> {
-        self.base.owner_mut()
+    fn on_collision(&mut self, ctx: &mut ComponentContext, event: &CollisionEvent) {
+        // 小惑星にぶつかったら破壊される（無敵中は無視）
+        if event.other_has::<Asteroid>() && !self.is_invulnerable() {
+            ctx.owner_mut().set_status(ActorStatus::Dead);
+            let pos = ctx.owner().position();
+            ctx.commands()
+                .spawn(move |world| world.create_explosion(pos));
+        }
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
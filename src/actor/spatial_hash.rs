@@ -0,0 +1,194 @@
+use super::vector2::Vector2;
+use std::collections::HashMap;
+
+/// 一様グリッドによる空間ハッシュ
+///
+/// 円をその外接矩形が重なる全てのセルに登録し、同じセルに入った円の組を
+/// 衝突候補として返す。セルの大きさを最大の円の直径以上にしておけば、
+/// 1つの円が登録されるセルは高々4つで済む。
+pub struct SpatialHash {
+    cell_size: Vector2,
+    /// 画面端で折り返す場合の列数と行数
+    grid: Option<(i32, i32)>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        let cell_size = cell_size.max(f32::EPSILON);
+        Self {
+            cell_size: Vector2::new(cell_size, cell_size),
+            grid: None,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// 登録済みの円を全て取り除き、セルの大きさを設定し直す
+    pub fn reset(&mut self, cell_size: f32) {
+        let cell_size = cell_size.max(f32::EPSILON);
+        self.cell_size = Vector2::new(cell_size, cell_size);
+        self.grid = None;
+        self.cells.clear();
+    }
+
+    /// 登録済みの円を全て取り除き、world_sizeの範囲で折り返すグリッドにする
+    ///
+    /// 画面をちょうど割り切れるように、セルはcell_size以上の大きさに広げる。
+    pub fn reset_wrapped(&mut self, cell_size: f32, world_size: Vector2) {
+        let cell_size = cell_size.max(f32::EPSILON);
+        let columns = ((world_size.x / cell_size).floor() as i32).max(1);
+        let rows = ((world_size.y / cell_size).floor() as i32).max(1);
+        self.cell_size = Vector2::new(world_size.x / columns as f32, world_size.y / rows as f32);
+        self.grid = Some((columns, rows));
+        self.cells.clear();
+    }
+
+    /// 座標を含むセル
+    fn cell(&self, position: Vector2) -> (i32, i32) {
+        (
+            (position.x / self.cell_size.x).floor() as i32,
+            (position.y / self.cell_size.y).floor() as i32,
+        )
+    }
+
+    /// index番目の円を登録
+    pub fn insert(&mut self, index: usize, center: Vector2, radius: f32) {
+        let extent = Vector2::new(radius, radius);
+        let (min_x, min_y) = self.cell(center - extent);
+        let (max_x, max_y) = self.cell(center + extent);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                let key = match self.grid {
+                    Some((columns, rows)) => (x.rem_euclid(columns), y.rem_euclid(rows)),
+                    None => (x, y),
+                };
+                self.cells.entry(key).or_default().push(index);
+            }
+        }
+    }
+
+    /// 同じセルに入っている円の組を重複なく返す（(小さい番号, 大きい番号)の順）
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for indices in self.cells.values() {
+            for (i, &a) in indices.iter().enumerate() {
+                for &b in &indices[i + 1..] {
+                    // 折り返しで同じ円が1つのセルに2回入ることがある
+                    if a != b {
+                        pairs.push((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+        // 複数のセルにまたがる円の組は複数回現れるので取り除く
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::math::Math;
+    use crate::actor::random::Random;
+
+    /// 中心と半径の組
+    type Circle = (Vector2, f32);
+
+    /// 候補のうち実際に重なっている組
+    fn hashed_pairs(
+        hash: &SpatialHash,
+        circles: &[Circle],
+        world_size: Option<Vector2>,
+    ) -> Vec<(usize, usize)> {
+        hash.candidate_pairs()
+            .into_iter()
+            .filter(|&(a, b)| overlaps(circles[a], circles[b], world_size))
+            .collect()
+    }
+
+    /// 全ての組み合わせを調べて重なっている組
+    fn brute_force_pairs(circles: &[Circle], world_size: Option<Vector2>) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for a in 0..circles.len() {
+            for b in a + 1..circles.len() {
+                if overlaps(circles[a], circles[b], world_size) {
+                    pairs.push((a, b));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn overlaps((a, radius_a): Circle, (b, radius_b): Circle, world_size: Option<Vector2>) -> bool {
+        let mut diff = b - a;
+        if let Some(size) = world_size {
+            diff = Vector2::new(
+                Math::wrapped_delta(diff.x, size.x),
+                Math::wrapped_delta(diff.y, size.y),
+            );
+        }
+        let radii = radius_a + radius_b;
+        diff.length_squared() <= radii * radii
+    }
+
+    #[test]
+    fn matches_brute_force_with_bodies_larger_than_cells() {
+        let mut random = Random::new(1);
+        let circles: Vec<Circle> = (0..300)
+            .map(|_| {
+                let center = random.vector_in_rect(-200.0, 600.0, -200.0, 400.0);
+                (center, random.float_range(2.0, 80.0))
+            })
+            .collect();
+
+        // セルは半径の大きい円よりずっと小さい
+        let mut hash = SpatialHash::new(16.0);
+        for (index, &(center, radius)) in circles.iter().enumerate() {
+            hash.insert(index, center, radius);
+        }
+        let expected = brute_force_pairs(&circles, None);
+        assert!(!expected.is_empty());
+        assert_eq!(hashed_pairs(&hash, &circles, None), expected);
+    }
+
+    #[test]
+    fn matches_brute_force_across_wrapped_edges() {
+        let world_size = Vector2::new(320.0, 240.0);
+        let mut random = Random::new(2);
+        // 縦か横のどちらかの端の近くに集めて、端をまたぐ組を多くする
+        let near_edge =
+            |random: &mut Random, size: f32| Math::wrap(random.float_range(-15.0, 15.0), size);
+        let circles: Vec<Circle> = (0..200)
+            .map(|_| {
+                let center = if random.float() < 0.0 {
+                    Vector2::new(
+                        near_edge(&mut random, world_size.x),
+                        random.float_range(0.0, world_size.y),
+                    )
+                } else {
+                    Vector2::new(
+                        random.float_range(0.0, world_size.x),
+                        near_edge(&mut random, world_size.y),
+                    )
+                };
+                (center, random.float_range(1.0, 50.0))
+            })
+            .collect();
+
+        for cell_size in [10.0, 100.0] {
+            let mut hash = SpatialHash::new(1.0);
+            hash.reset_wrapped(cell_size, world_size);
+            for (index, &(center, radius)) in circles.iter().enumerate() {
+                hash.insert(index, center, radius);
+            }
+            let expected = brute_force_pairs(&circles, Some(world_size));
+            // 端をまたがないと見つからない組がある
+            assert!(expected
+                .iter()
+                .any(|&(a, b)| !overlaps(circles[a], circles[b], None)));
+            assert_eq!(hashed_pairs(&hash, &circles, Some(world_size)), expected);
+        }
+    }
+}
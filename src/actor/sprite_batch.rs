@@ -0,0 +1,120 @@
+use super::sprite_instance::SpriteInstance;
+use super::texture::Texture;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use wgpu;
+
+/// 同じ描画順・同じテクスチャのスプライトのまとまり
+struct SpriteGroup {
+    draw_order: i32,
+    texture: Arc<Texture>,
+    instances: Vec<SpriteInstance>,
+}
+
+/// スプライトを描画順・テクスチャごとにまとめ、インスタンス描画で一括描画する
+///
+/// 描画順が異なるスプライトは必ず描画順どおりに描かれる。
+/// 同じ描画順の中ではテクスチャ単位でまとめて描画する。
+pub struct SpriteBatch {
+    /// 描画順・テクスチャごとのスプライト
+    groups: Vec<SpriteGroup>,
+    /// 描画順とテクスチャのアドレスからグループの位置を引くためのマップ
+    group_indices: HashMap<(i32, *const Texture), usize>,
+    /// 描画コール単位のテクスチャとインスタンス範囲
+    draw_calls: Vec<(Arc<Texture>, Range<u32>)>,
+    instance_buffer: wgpu::Buffer,
+    /// インスタンスバッファに格納できるスプライト数
+    capacity: usize,
+}
+
+impl SpriteBatch {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let capacity = 64;
+        Self {
+            groups: Vec::new(),
+            group_indices: HashMap::new(),
+            draw_calls: Vec::new(),
+            instance_buffer: Self::create_instance_buffer(device, capacity),
+            capacity,
+        }
+    }
+
+    /// インスタンスバッファを作成
+    fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sprite Instance Buffer"),
+            size: (capacity * std::mem::size_of::<SpriteInstance>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// 前フレームの内容を破棄して新しいフレームの収集を始める
+    pub fn begin(&mut self) {
+        // グループのVecは使い回してフレームごとの確保を減らす
+        for group in &mut self.groups {
+            group.instances.clear();
+        }
+        self.draw_calls.clear();
+    }
+
+    /// スプライトを1つ追加
+    pub fn push(&mut self, draw_order: i32, texture: &Arc<Texture>, instance: SpriteInstance) {
+        let key = (draw_order, Arc::as_ptr(texture));
+        let index = match self.group_indices.get(&key) {
+            Some(&index) => index,
+            None => {
+                self.groups.push(SpriteGroup {
+                    draw_order,
+                    texture: texture.clone(),
+                    instances: Vec::new(),
+                });
+                self.group_indices.insert(key, self.groups.len() - 1);
+                self.groups.len() - 1
+            }
+        };
+        self.groups[index].instances.push(instance);
+    }
+
+    /// 集めたスプライトをインスタンスバッファに書き込み、描画コールを組み立てる
+    pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        // 今フレームで使われなかったグループは手放し、描画順に並べ直す
+        // （安定ソートなので同じ描画順のグループの順番は前フレームから変わらない）
+        self.groups.retain(|group| !group.instances.is_empty());
+        self.groups.sort_by_key(|group| group.draw_order);
+        self.group_indices = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(index, group)| ((group.draw_order, Arc::as_ptr(&group.texture)), index))
+            .collect();
+
+        let mut instances = Vec::new();
+        for group in &self.groups {
+            let start = instances.len() as u32;
+            instances.extend_from_slice(&group.instances);
+            self.draw_calls
+                .push((group.texture.clone(), start..instances.len() as u32));
+        }
+
+        // 容量が足りなければインスタンスバッファを作り直す
+        if instances.len() > self.capacity {
+            self.capacity = instances.len().next_power_of_two();
+            self.instance_buffer = Self::create_instance_buffer(device, self.capacity);
+        }
+        queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(&instances));
+    }
+
+    /// グループごとに1回のインスタンス描画を発行
+    pub fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.draw_calls.is_empty() {
+            return;
+        }
+        render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        for (texture, range) in &self.draw_calls {
+            render_pass.set_bind_group(0, &texture.bind_group, &[]);
+            render_pass.draw(0..6, range.clone()); // 6頂点 × インスタンス数
+        }
+    }
+}
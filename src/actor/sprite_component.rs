@@ -1,31 +1,52 @@
 use super::actor::Actor;
-use super::component::{Component, ComponentBase};
+use super::component::{Component, ComponentContext};
+use super::sprite_instance::SpriteInstance;
 use super::texture::Texture;
 use super::texture::TextureManager;
 use std::sync::Arc;
-use wgpu;
 
+/// 背景の描画順
+pub const DRAW_ORDER_BACKGROUND: i32 = 0;
+/// 小惑星の描画順
+pub const DRAW_ORDER_ASTEROID: i32 = 100;
+/// 宇宙船の描画順
+pub const DRAW_ORDER_SHIP: i32 = 150;
+/// レーザーの描画順
+pub const DRAW_ORDER_LASER: i32 = 200;
+/// HUDの描画順
+pub const DRAW_ORDER_HUD: i32 = 1000;
+/// シーン切り替えのフェードの描画順（全ての上に描く）
+pub const DRAW_ORDER_FADE: i32 = 10000;
+
+/// テクスチャを描画するコンポーネント
+///
+/// テクスチャは名前で指定し、描画の前にTextureManagerから読み込まれる。
+/// 実行中にテクスチャを差し替えるときも、request_textureで名前を予約しておけばよい。
 pub struct SpriteComponent {
-    base: ComponentBase,
     texture_height: i32,
     texture_width: i32,
     draw_order: i32,
     texture_name: String,
     texture: Option<Arc<Texture>>,
+    /// 次の描画までに切り替えるテクスチャの名前
+    pending_texture: Option<String>,
+    /// 読み込めなかったテクスチャの名前（毎フレーム読み込み直さないようにする）
+    failed_texture: Option<String>,
+    visible: bool,
 }
 
 impl SpriteComponent {
-    pub fn new(texture_name: &str, draw_order: i32, texture_manager: &mut TextureManager) -> Self {
-        texture_manager.load_texture(texture_name);
-        let texture = texture_manager.get_texture(texture_name);
-
+    /// 名前で指定したテクスチャを描くスプライトを作成（テクスチャは最初の描画の前に読み込む）
+    pub fn new(texture_name: &str, draw_order: i32) -> Self {
         Self {
-            base: ComponentBase::new(),
             texture_height: 0,
             texture_width: 0,
             draw_order,
             texture_name: texture_name.to_string(),
-            texture,
+            texture: None,
+            pending_texture: Some(texture_name.to_string()),
+            failed_texture: None,
+            visible: true,
         }
     }
 
@@ -41,38 +62,82 @@ impl SpriteComponent {
         self.draw_order
     }
 
-    // テクスチャの設定メソッド
-    // 注: 実際のテクスチャ管理は別途実装が必要
-    pub fn set_texture(&mut self, width: i32, height: i32) {
-        self.texture_width = width;
-        self.texture_height = height;
+    pub fn texture_name(&self) -> &str {
+        &self.texture_name
     }
 
-    // 描画メソッド
-    // 注: 実際の描画処理は別途実装が必要
-    pub fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-        if let (Some(actor), Some(texture)) = (self.owner(), &self.texture) {
-            render_pass.set_bind_group(0, &texture.bind_group, &[]);
-            render_pass.draw(0..6, 0..1); // 6頂点を描画
+    /// テクスチャをすぐに切り替える（読み込めなければ今のテクスチャのまま）
+    pub fn set_texture(&mut self, texture_name: &str, texture_manager: &mut TextureManager) {
+        self.pending_texture = None;
+        if texture_name == self.texture_name && self.texture.is_some() {
+            return;
         }
+        texture_manager.load_texture(texture_name);
+        let Some(texture) = texture_manager.get_texture(texture_name) else {
+            println!("Failed to switch sprite texture: {}", texture_name);
+            self.failed_texture = Some(texture_name.to_string());
+            return;
+        };
+        self.failed_texture = None;
+        self.texture_width = texture.size.0 as i32;
+        self.texture_height = texture.size.1 as i32;
+        self.texture_name = texture_name.to_string();
+        self.texture = Some(texture);
     }
-}
 
-impl Component for SpriteComponent {
-    fn update(&mut self, _delta_time: f32) {
-        // スプライトの更新処理が必要な場合はここに実装
+    /// テクスチャの切り替えを予約する（TextureManagerに触れないコンポーネントから使う）
+    ///
+    /// 最後に読み込めなかったテクスチャは予約しない（失敗を毎フレーム繰り返さない）。
+    pub fn request_texture(&mut self, texture_name: &str) {
+        let current = texture_name == self.texture_name && self.texture.is_some();
+        if current || self.failed_texture.as_deref() == Some(texture_name) {
+            self.pending_texture = None;
+        } else {
+            self.pending_texture = Some(texture_name.to_string());
+        }
     }
 
-    fn set_owner(&mut self, owner: &mut Actor) {
-        self.base.set_owner(owner);
+    /// 予約されたテクスチャに切り替える
+    pub fn resolve_texture(&mut self, texture_manager: &mut TextureManager) {
+        if let Some(texture_name) = self.pending_texture.take() {
+            self.set_texture(&texture_name, texture_manager);
+        }
     }
 
-    fn owner(&self) -> Option<&Actor> {
-        self.base.owner()
+    pub fn is_visible(&self) -> bool {
+        self.visible
     }
 
-    fn owner_mut(&mut self) -> Option<&mut Actor> {
-        self.base.owner_mut()
+    /// 描画するかどうかを設定（点滅などに使う）
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// 所有者アクターのトランスフォームからインスタンスデータを作成（非表示ならNone）
+    ///
+    /// alphaは前回の更新から今回の更新までの補間の割合（1.0なら現在の位置）。
+    pub fn instance(&self, actor: &Actor, alpha: f32) -> Option<SpriteInstance> {
+        if !self.visible {
+            return None;
+        }
+        self.texture.as_ref().map(|texture| {
+            SpriteInstance::new(
+                actor.interpolated_position(alpha),
+                actor.interpolated_rotation(alpha),
+                actor.scale(),
+                texture.size,
+            )
+        })
+    }
+
+    pub fn texture(&self) -> Option<&Arc<Texture>> {
+        self.texture.as_ref()
+    }
+}
+
+impl Component for SpriteComponent {
+    fn update(&mut self, _ctx: &mut ComponentContext, _delta_time: f32) {
+        // スプライトの更新処理が必要な場合はここに実装
     }
 
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
@@ -0,0 +1,93 @@
+use super::vector2::Vector2;
+use bytemuck::{Pod, Zeroable};
+use wgpu;
+
+/// スプライト1つ分のトランスフォームと見た目（インスタンスバッファに書き込む）
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub struct SpriteInstance {
+    /// ワールド座標での中心位置
+    pub position: [f32; 2],
+    /// ピクセル単位の描画サイズ（テクスチャサイズ × スケール）
+    pub size: [f32; 2],
+    /// 回転角度（ラジアン）
+    pub rotation: f32,
+    /// テクスチャ内の描画範囲の左上（0.0〜1.0）
+    pub uv_offset: [f32; 2],
+    /// テクスチャ内の描画範囲の大きさ（0.0〜1.0）
+    pub uv_size: [f32; 2],
+    /// テクスチャの色に掛ける色（RGBA）
+    pub color: [f32; 4],
+}
+
+impl SpriteInstance {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 6] = wgpu::vertex_attr_array![
+        0 => Float32x2,
+        1 => Float32x2,
+        2 => Float32,
+        3 => Float32x2,
+        4 => Float32x2,
+        5 => Float32x4
+    ];
+
+    /// 色を変えない（白）
+    pub const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+    /// アクターのトランスフォームとテクスチャサイズからインスタンスを作成（テクスチャ全体を描く）
+    pub fn new(position: Vector2, rotation: f32, scale: Vector2, texture_size: (u32, u32)) -> Self {
+        Self {
+            position: [position.x, position.y],
+            size: [
+                texture_size.0 as f32 * scale.x,
+                texture_size.1 as f32 * scale.y,
+            ],
+            rotation,
+            uv_offset: [0.0, 0.0],
+            uv_size: [1.0, 1.0],
+            color: Self::WHITE,
+        }
+    }
+
+    /// テクスチャの一部だけを描くようにする
+    pub fn with_uv(mut self, uv_offset: [f32; 2], uv_size: [f32; 2]) -> Self {
+        self.uv_offset = uv_offset;
+        self.uv_size = uv_size;
+        self
+    }
+
+    /// テクスチャの色に掛ける色を設定
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// インスタンスバッファのレイアウト
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<SpriteInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// シェーダーに渡すカメラ行列
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+pub struct CameraUniform {
+    pub view_proj: [[f32; 4]; 4],
+}
+
+impl CameraUniform {
+    /// 左上原点・Y軸下向きのピクセル座標をクリップ空間に変換する正射影行列を作成
+    pub fn orthographic(width: f32, height: f32) -> Self {
+        Self {
+            view_proj: [
+                [2.0 / width, 0.0, 0.0, 0.0],
+                [0.0, -2.0 / height, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [-1.0, 1.0, 0.0, 1.0],
+            ],
+        }
+    }
+}
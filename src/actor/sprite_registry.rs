@@ -0,0 +1,53 @@
+use super::actor_id::ActorId;
+
+/// 描画順に並べたスプライト1つ分の登録情報
+#[derive(Debug, Copy, Clone)]
+struct SpriteEntry {
+    draw_order: i32,
+    actor_id: ActorId,
+}
+
+/// スプライトを持つアクターを描画順（draw_order昇順）に保持するレジストリ
+///
+/// draw_orderが同じ場合は登録された順に並ぶため、実行ごとに順番が変わらない。
+pub struct SpriteRegistry {
+    entries: Vec<SpriteEntry>,
+}
+
+impl Default for SpriteRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpriteRegistry {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// スプライトを登録（同じdraw_orderの中では最後尾に入る）
+    pub fn insert(&mut self, actor_id: ActorId, draw_order: i32) {
+        let index = self
+            .entries
+            .partition_point(|entry| entry.draw_order <= draw_order);
+        self.entries.insert(
+            index,
+            SpriteEntry {
+                draw_order,
+                actor_id,
+            },
+        );
+    }
+
+    /// アクターのスプライトを登録解除
+    pub fn remove(&mut self, actor_id: ActorId) {
+        self.entries.retain(|entry| entry.actor_id != actor_id);
+    }
+
+    /// 描画順にアクターIDを返す
+    pub fn iter(&self) -> impl Iterator<Item = ActorId> + '_ {
+        self.entries.iter().map(|entry| entry.actor_id)
+    }
+}
@@ -77,10 +77,17 @@ impl TextureManager {
         let dimensions = img.dimensions();
         println!("Image dimensions: {}x{}", dimensions.0, dimensions.1);
 
+        self.add_texture(filename, dimensions, &img.to_rgba8());
+        println!("Texture successfully created and stored: {}", filename);
+        Some(self.textures.len() as u32)
+    }
+
+    /// RGBAのピクセルデータから名前付きのテクスチャを作成（同じ名前があれば置き換える）
+    pub fn add_texture(&mut self, name: &str, dimensions: (u32, u32), rgba: &[u8]) {
         let texture = self.device.create_texture_with_data(
             &self.queue,
             &wgpu::TextureDescriptor {
-                label: Some(filename),
+                label: Some(name),
                 size: wgpu::Extent3d {
                     width: dimensions.0,
                     height: dimensions.1,
@@ -93,7 +100,7 @@ impl TextureManager {
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
             },
-            &img.to_rgba8(),
+            rgba,
         );
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -108,7 +115,7 @@ impl TextureManager {
         });
 
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some(filename),
+            label: Some(name),
             layout: &self.bind_group_layout,
             entries: &[
                 wgpu::BindGroupEntry {
@@ -123,7 +130,7 @@ impl TextureManager {
         });
 
         self.textures.insert(
-            filename.to_string(),
+            name.to_string(),
             Arc::new(Texture {
                 texture,
                 view,
@@ -132,9 +139,6 @@ impl TextureManager {
                 size: dimensions,
             }),
         );
-
-        println!("Texture successfully created and stored: {}", filename);
-        Some(self.textures.len() as u32)
     }
 
     pub fn get_texture(&self, filename: &str) -> Option<Arc<Texture>> {
@@ -0,0 +1,117 @@
+use super::bitmap_font::TextAlign;
+use super::high_score::HighScoreEntry;
+use super::input_map::Action;
+use super::playing_scene::PlayingScene;
+use super::render_context::RenderContext;
+use super::scene::{Scene, SceneContext, SceneInput, SceneTransition};
+use super::sprite_instance::SpriteInstance;
+use super::vector2::Vector2;
+use super::wave::WaveConfig;
+use super::world::World;
+
+/// 「PRESS ENTER」を点滅させる周期（秒）
+const PROMPT_BLINK_INTERVAL: f32 = 0.5;
+
+/// タイトル画面
+///
+/// 背景ではプレイヤーのいない世界で小惑星が漂い、ハイスコア表を表示する。
+pub struct TitleScene {
+    world: World,
+    /// 表示するハイスコア
+    high_scores: Vec<HighScoreEntry>,
+    /// 表示してからの経過時間
+    time: f32,
+}
+
+impl TitleScene {
+    pub fn new(ctx: &mut SceneContext) -> Self {
+        Self {
+            world: World::new(ctx.world_size(), WaveConfig::default(), ctx.next_seed()),
+            high_scores: ctx.high_scores().entries().to_vec(),
+            time: 0.0,
+        }
+    }
+}
+
+impl Scene for TitleScene {
+    fn name(&self) -> &str {
+        "Title"
+    }
+
+    fn process_input(&mut self, ctx: &mut SceneContext, input: &SceneInput) -> SceneTransition {
+        if input.just_pressed(Action::Confirm) {
+            SceneTransition::Replace(Box::new(PlayingScene::new(ctx)))
+        } else if input.just_pressed(Action::Quit) {
+            SceneTransition::Quit
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn update(&mut self, _ctx: &mut SceneContext, delta_time: f32) -> SceneTransition {
+        self.time += delta_time;
+        self.world.update(delta_time, &[]);
+        SceneTransition::None
+    }
+
+    fn render(&mut self, ctx: &mut RenderContext) {
+        ctx.draw_world(&mut self.world);
+
+        let screen_size = ctx.screen_size();
+        let center_x = screen_size.x * 0.5;
+        ctx.draw_text(
+            "ASTEROIDS",
+            Vector2::new(center_x, screen_size.y * 0.15),
+            4.0,
+            SpriteInstance::WHITE,
+            TextAlign::Center,
+        );
+
+        let mut y = screen_size.y * 0.35;
+        if !self.high_scores.is_empty() {
+            ctx.draw_text(
+                "HIGH SCORES",
+                Vector2::new(center_x, y),
+                1.5,
+                SpriteInstance::WHITE,
+                TextAlign::Center,
+            );
+            y += 40.0;
+            for (rank, entry) in self.high_scores.iter().enumerate() {
+                let line = format!(
+                    "{:>2}. {} {:>8}  WAVE {}",
+                    rank + 1,
+                    entry.initials(),
+                    entry.score(),
+                    entry.wave()
+                );
+                ctx.draw_text(
+                    &line,
+                    Vector2::new(center_x, y),
+                    1.0,
+                    SpriteInstance::WHITE,
+                    TextAlign::Center,
+                );
+                y += 24.0;
+            }
+        }
+
+        if self.time % (PROMPT_BLINK_INTERVAL * 2.0) < PROMPT_BLINK_INTERVAL {
+            ctx.draw_text(
+                "PRESS ENTER",
+                Vector2::new(center_x, screen_size.y * 0.85),
+                2.0,
+                SpriteInstance::WHITE,
+                TextAlign::Center,
+            );
+        }
+    }
+
+    fn resize(&mut self, world_size: Vector2) {
+        self.world.set_world_size(world_size);
+    }
+
+    fn world(&self) -> Option<&World> {
+        Some(&self.world)
+    }
+}
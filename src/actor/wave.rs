@@ -0,0 +1,244 @@
+use super::stable_hasher::StableHasher;
+use std::fs;
+use std::hash::Hasher;
+use std::path::Path;
+
+/// 小惑星の速度の上限（ウェーブを重ねて速くなりすぎないようにする）
+pub const MAX_ASTEROID_SPEED: f32 = 600.0;
+
+/// 1ウェーブ分の小惑星の設定
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WaveParams {
+    /// 出現させる小惑星の数
+    pub asteroid_count: u32,
+    /// 小惑星の最低速度
+    pub min_speed: f32,
+    /// 小惑星の最高速度
+    pub max_speed: f32,
+}
+
+/// ウェーブ進行の設定（データファイルから読み込む）
+///
+/// ファイルは1行に1つの設定を書き、`#`以降はコメントとして扱う。値は0以上の有限の数に限る。
+///
+/// - `wave <数> <最低速度> <最高速度>`: ウェーブを1つ定義する（書いた順に1, 2, 3, ...）
+/// - `escalate <増やす数> <速度の倍率>`: 定義したウェーブより後で1ウェーブごとに適用する増加量
+/// - `max_asteroids <数>`: 1ウェーブに出す小惑星の上限
+/// - `wave_delay <秒>`: ウェーブを全滅させてから次のウェーブが始まるまでの時間
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveConfig {
+    waves: Vec<WaveParams>,
+    count_step: u32,
+    speed_multiplier: f32,
+    max_asteroids: u32,
+    wave_delay: f32,
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        Self {
+            waves: vec![WaveParams {
+                asteroid_count: 4,
+                min_speed: 80.0,
+                max_speed: 150.0,
+            }],
+            count_step: 1,
+            speed_multiplier: 1.1,
+            max_asteroids: 12,
+            wave_delay: 2.0,
+        }
+    }
+}
+
+impl WaveConfig {
+    /// ファイルから読み込む（読めなければ標準の設定を使う）
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        println!("Loading wave config: {:?}", path);
+        let result = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Self::parse(&text));
+        match result {
+            Ok(config) => config,
+            Err(e) => {
+                println!("Failed to load wave config: {:?} - Error: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// 設定ファイルの内容を解析
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut waves = Vec::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let key = fields.next().unwrap_or("");
+            let values: Vec<f32> = fields
+                .map(|field| field.parse::<f32>())
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("line {}: {}", line_number + 1, e))?;
+            if values
+                .iter()
+                .any(|value| !value.is_finite() || *value < 0.0)
+            {
+                return Err(format!(
+                    "line {}: values must be finite and not negative",
+                    line_number + 1
+                ));
+            }
+            let expect = |count: usize| {
+                if values.len() == count {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "line {}: `{}` expects {} values",
+                        line_number + 1,
+                        key,
+                        count
+                    ))
+                }
+            };
+
+            match key {
+                "wave" => {
+                    expect(3)?;
+                    waves.push(WaveParams {
+                        asteroid_count: values[0] as u32,
+                        min_speed: values[1],
+                        max_speed: values[2].max(values[1]),
+                    });
+                }
+                "escalate" => {
+                    expect(2)?;
+                    config.count_step = values[0] as u32;
+                    config.speed_multiplier = values[1];
+                }
+                "max_asteroids" => {
+                    expect(1)?;
+                    config.max_asteroids = values[0] as u32;
+                }
+                "wave_delay" => {
+                    expect(1)?;
+                    config.wave_delay = values[0];
+                }
+                _ => return Err(format!("line {}: unknown key `{}`", line_number + 1, key)),
+            }
+        }
+
+        if !waves.is_empty() {
+            config.waves = waves;
+        }
+        Ok(config)
+    }
+
+    /// wave番目（1から数える）のウェーブの設定
+    pub fn params(&self, wave: u32) -> WaveParams {
+        let index = (wave.max(1) - 1) as usize;
+        let mut params = match self.waves.get(index) {
+            Some(params) => *params,
+            None => {
+                // 定義より後のウェーブは最後の定義から増やしていく
+                let last = *self.waves.last().unwrap();
+                let extra = (index + 1 - self.waves.len()) as u32;
+                let speed_scale = self
+                    .speed_multiplier
+                    .powi(extra.min(i32::MAX as u32) as i32);
+                // 倍率は無限大になりうるので、速度0の定義は0のままにする（0 * ∞はNaN）
+                let scale = |speed: f32| {
+                    if speed > 0.0 {
+                        speed * speed_scale
+                    } else {
+                        0.0
+                    }
+                };
+                WaveParams {
+                    asteroid_count: last
+                        .asteroid_count
+                        .saturating_add(self.count_step.saturating_mul(extra)),
+                    min_speed: scale(last.min_speed),
+                    max_speed: scale(last.max_speed),
+                }
+            }
+        };
+        params.asteroid_count = params.asteroid_count.min(self.max_asteroids);
+        // 何ウェーブ進んでも上限より速くならないようにする
+        params.min_speed = params.min_speed.min(MAX_ASTEROID_SPEED);
+        params.max_speed = params.max_speed.min(MAX_ASTEROID_SPEED);
+        params
+    }
+
+    pub fn wave_delay(&self) -> f32 {
+        self.wave_delay
+    }
+
+    /// 全ての設定値をハッシュに加える（リプレイの設定の照合に使う）
+    pub fn write_hash(&self, hasher: &mut StableHasher) {
+        hasher.write_u64(self.waves.len() as u64);
+        for wave in &self.waves {
+            hasher.write_u32(wave.asteroid_count);
+            hasher.write_f32(wave.min_speed);
+            hasher.write_f32(wave.max_speed);
+        }
+        hasher.write_u32(self.count_step);
+        hasher.write_f32(self.speed_multiplier);
+        hasher.write_u32(self.max_asteroids);
+        hasher.write_f32(self.wave_delay);
+    }
+}
+
+/// ウェーブの進行状況を管理する
+///
+/// 小惑星が全滅したことを検出し、一定時間後に次のウェーブを始める。
+pub struct WaveManager {
+    config: WaveConfig,
+    /// 現在のウェーブ番号（まだ始まっていなければ0）
+    wave: u32,
+    /// 次のウェーブが始まるまでの残り時間（待っていなければNone）
+    next_wave_timer: Option<f32>,
+}
+
+impl WaveManager {
+    pub fn new(config: WaveConfig) -> Self {
+        Self {
+            config,
+            wave: 0,
+            // 最初のウェーブはすぐに始める
+            next_wave_timer: Some(0.0),
+        }
+    }
+
+    /// 現在のウェーブ番号
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    /// 残っている小惑星の数を受け取って進行を更新し、
+    /// 新しいウェーブを始めるときはその設定を返す
+    pub fn update(&mut self, delta_time: f32, asteroid_count: usize) -> Option<WaveParams> {
+        match self.next_wave_timer {
+            Some(timer) => {
+                let timer = timer - delta_time;
+                if timer > 0.0 {
+                    self.next_wave_timer = Some(timer);
+                    return None;
+                }
+                self.next_wave_timer = None;
+                self.wave += 1;
+                Some(self.config.params(self.wave))
+            }
+            None => {
+                if asteroid_count == 0 {
+                    self.next_wave_timer = Some(self.config.wave_delay());
+                }
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,701 @@
+use super::actor::Actor;
+use super::actor_id::ActorId;
+use super::actor_storage::ActorStorage;
+use super::asteroid::{Asteroid, AsteroidSize};
+use super::circle_component::CircleComponent;
+use super::collision::{CollisionBody, CollisionEvent, CollisionSystem};
+use super::collision_layers::CollisionLayers;
+use super::commands::{Command, Commands};
+use super::component::Component;
+use super::debris::Debris;
+use super::input_component::InputComponent;
+use super::input_map::Action;
+use super::laser::Laser;
+use super::math::Math;
+use super::player::{Player, PlayerState};
+use super::random::{Random, RandomStream, RandomStreams};
+use super::rigid_body_component::RigidBodyComponent;
+use super::score::{Score, ScoreConfig, ScoreSource};
+use super::ship::{Ship, SHIP_TEXTURE};
+use super::sprite_component::{
+    SpriteComponent, DRAW_ORDER_ASTEROID, DRAW_ORDER_LASER, DRAW_ORDER_SHIP,
+};
+use super::sprite_registry::SpriteRegistry;
+use super::stable_hasher::StableHasher;
+use super::texture::TextureManager;
+use super::vector2::Vector2;
+use super::wave::{WaveConfig, WaveManager, WaveParams};
+use super::wrap_component::WrapComponent;
+use std::f32::consts::PI;
+use std::hash::Hasher;
+
+/// 得点・残機・ウェーブの現在の状態
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GameStats {
+    pub score: u32,
+    pub lives: u32,
+    pub wave: u32,
+    pub game_over: bool,
+}
+
+/// ワールドで起きた出来事（HUDの表示などに使う）
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    /// ウェーブが始まった（ウェーブ番号）
+    WaveStarted(u32),
+    /// 得点で残機が増えた
+    ExtraLife,
+    /// 宇宙船が壊された
+    ShipDestroyed,
+    /// 残機がなくなった
+    GameOver,
+}
+
+/// 宇宙船が復活するために、出現位置の周りで小惑星がいてはいけない範囲の半径
+const SPAWN_CLEAR_RADIUS: f32 = 150.0;
+
+/// 1フレームで予約された命令を適用し直す回数の上限
+///
+/// 生成処理が毎回さらに生成を予約しても止まるようにする。残った命令は次のフレームで適用し、
+/// その回数は`World::deferred_command_frames`で数える。
+const MAX_COMMAND_PASSES: usize = 8;
+
+/// アクターとそれを動かすシステムをまとめたゲームの世界
+///
+/// 描画やウィンドウには依存しない。シーンごとに1つずつ持つ。
+pub struct World {
+    /// アクティブなアクターのマップ
+    actors: ActorStorage,
+    /// 更新中に予約されたアクターの生成・削除
+    commands: Commands,
+    /// アクター同士の衝突判定
+    collision_system: CollisionSystem,
+    /// 衝突レイヤーの名前とビットの対応
+    collision_layers: CollisionLayers,
+    /// 描画順に並べたスプライトを持つアクター
+    sprites: SpriteRegistry,
+    /// 小惑星のウェーブの進行
+    wave_manager: WaveManager,
+    /// 残機と宇宙船の復活の進行（プレイヤーがいない世界ならNone）
+    player: Option<Player>,
+    /// プレイヤーの宇宙船のアクター
+    player_id: Option<ActorId>,
+    /// 得点
+    score: Score,
+    /// まだ取り出されていない出来事
+    events: Vec<GameEvent>,
+    /// 用途ごとに分けたランダム値生成
+    random: RandomStreams,
+    /// 次にゲームの進行に関わるアクターに付ける生成順
+    next_spawn_order: u64,
+    /// 命令を適用しきれずに次のフレームへ持ち越した回数
+    deferred_command_frames: u64,
+    /// 画面（ゲーム空間）の大きさ
+    world_size: Vector2,
+    /// 今回の更新の経過時間
+    delta_time: f32,
+}
+
+impl World {
+    /// 小惑星のウェーブだけが進む、プレイヤーのいない世界を作成
+    ///
+    /// 同じシードと同じ入力を与えれば、毎回同じように進む。
+    pub fn new(world_size: Vector2, wave_config: WaveConfig, seed: u64) -> Self {
+        let mut collision_system = CollisionSystem::new();
+        collision_system.set_world_size(Some(world_size));
+        Self {
+            actors: ActorStorage::new(),
+            commands: Commands::new(),
+            collision_system,
+            collision_layers: CollisionLayers::default(),
+            sprites: SpriteRegistry::new(),
+            wave_manager: WaveManager::new(wave_config),
+            player: None,
+            player_id: None,
+            score: Score::new(ScoreConfig::default()),
+            events: Vec::new(),
+            random: RandomStreams::new(seed),
+            next_spawn_order: 0,
+            deferred_command_frames: 0,
+            world_size,
+            delta_time: 0.0,
+        }
+    }
+
+    /// 指定した残機でプレイヤーを参加させ、宇宙船を画面中央に出す
+    pub fn start_player(&mut self, lives: u32) {
+        self.player = Some(Player::new(lives));
+        let actor_id = self.add_actor();
+        self.setup_player_actor(actor_id);
+        if let Some(actor) = self.actors.get_mut(actor_id) {
+            actor.set_position(self.world_size * 0.5);
+            actor.set_rotation(-PI / 2.0);
+        }
+    }
+
+    /// 1フレーム分ワールドを進める（actionsはプレイヤーの操作）
+    pub fn update(&mut self, delta_time: f32, actions: &[Action]) {
+        self.delta_time = delta_time;
+        self.save_previous_transforms();
+        self.process_input(actions);
+        self.update_actors();
+        self.wrap_actors();
+        self.handle_collisions();
+        self.apply_commands();
+        self.update_waves();
+        self.update_player();
+    }
+
+    /// 新しいアクターを追加
+    pub fn add_actor(&mut self) -> ActorId {
+        let mut actor = Actor::new();
+        actor.set_spawn_order(Some(self.next_spawn_order));
+        self.next_spawn_order += 1;
+        self.actors.insert(actor)
+    }
+
+    /// 破片など見た目だけのアクターを追加
+    ///
+    /// 生成順を付けないので、衝突の処理の順番やチェックサムに影響しない。
+    /// 衝突判定のコンポーネントは付けないこと。
+    pub fn add_effect_actor(&mut self) -> ActorId {
+        self.actors.insert(Actor::new())
+    }
+
+    /// アクターを削除
+    ///
+    /// 既に削除されたアクターの古いハンドルを渡しても何も起きない。
+    pub fn remove_actor(&mut self, actor_id: ActorId) {
+        if self.actors.remove(actor_id).is_some() {
+            self.sprites.remove(actor_id);
+        }
+    }
+
+    /// アクターにコンポーネントを追加
+    ///
+    /// スプライトはここで描画順のレジストリに登録される。
+    pub fn add_component(&mut self, actor_id: ActorId, component: Box<dyn Component>) {
+        if let Some(actor) = self.actors.get_mut(actor_id) {
+            if let Some(sprite) = component
+                .as_ref()
+                .as_any()
+                .downcast_ref::<SpriteComponent>()
+            {
+                self.sprites.insert(actor_id, sprite.draw_order());
+            }
+            actor.add_component(component);
+        }
+    }
+
+    /// アクターから指定した型のコンポーネントを取り外す
+    pub fn remove_component<T: 'static>(
+        &mut self,
+        actor_id: ActorId,
+    ) -> Option<Box<dyn Component>> {
+        let component = self.actors.get_mut(actor_id)?.remove_component::<T>()?;
+        if component.as_ref().as_any().is::<SpriteComponent>() {
+            self.sprites.remove(actor_id);
+        }
+        Some(component)
+    }
+
+    /// アクターを取得
+    pub fn get_actor(&self, actor_id: ActorId) -> Option<&Actor> {
+        self.actors.get(actor_id)
+    }
+
+    /// アクターを可変で取得
+    pub fn get_actor_mut(&mut self, actor_id: ActorId) -> Option<&mut Actor> {
+        self.actors.get_mut(actor_id)
+    }
+
+    /// 全てのアクター
+    pub fn actors(&self) -> &ActorStorage {
+        &self.actors
+    }
+
+    /// 命令を適用しきれずに次のフレームへ持ち越した回数
+    pub fn deferred_command_frames(&self) -> u64 {
+        self.deferred_command_frames
+    }
+
+    /// スプライトを持つアクターを描画順に返す
+    pub fn sprite_actors(&self) -> impl Iterator<Item = ActorId> + '_ {
+        self.sprites.iter()
+    }
+
+    /// 描画時の補間のために更新前のトランスフォームを覚えておく
+    fn save_previous_transforms(&mut self) {
+        for (_, actor) in self.actors.iter_mut() {
+            actor.state_mut().save_previous_transform();
+        }
+    }
+
+    /// 入力処理
+    fn process_input(&mut self, actions: &[Action]) {
+        for (actor_id, actor) in self.actors.iter_mut() {
+            actor.process_input(actor_id, actions, &mut self.commands, &mut self.random);
+        }
+    }
+
+    /// 全てのアクターを更新
+    fn update_actors(&mut self) {
+        // アクティブな全てのアクターを更新
+        for (actor_id, actor) in self.actors.iter_mut() {
+            actor.update(
+                actor_id,
+                self.delta_time,
+                &mut self.commands,
+                &mut self.random,
+            );
+        }
+    }
+
+    /// WrapComponentを持つアクターを画面の反対側に折り返す
+    fn wrap_actors(&mut self) {
+        let world_size = self.world_size;
+        for (_, actor) in self.actors.iter_mut() {
+            if actor.get_component::<WrapComponent>().is_some() {
+                let pos = actor.position();
+                let wrapped = WrapComponent::wrap_position(pos, world_size);
+                actor.set_position(wrapped);
+                actor.state_mut().shift_previous_position(wrapped - pos);
+            }
+        }
+    }
+
+    /// CircleComponentを持つアクター同士の衝突を調べ、両方のアクターに通知する
+    fn handle_collisions(&mut self) {
+        let collisions = {
+            let mut bodies: Vec<CollisionBody> = self
+                .actors
+                .iter()
+                .filter(|(_, actor)| actor.is_active())
+                .filter_map(|(actor_id, actor)| {
+                    actor
+                        .get_component::<CircleComponent>()
+                        .map(|circle| CollisionBody {
+                            actor_id,
+                            owner: actor.state(),
+                            circle,
+                        })
+                })
+                .collect();
+            // 格納場所の位置は見た目だけのアクターの増減で変わるので、生成順に処理する
+            bodies.sort_by_key(|body| {
+                self.actors
+                    .get(body.actor_id)
+                    .and_then(|actor| actor.spawn_order())
+            });
+            self.collision_system.find_collisions(&bodies)
+        };
+
+        for collision in collisions {
+            let (actor_a, actor_b) = (collision.actor_a, collision.actor_b);
+            // 同じフレームの先の衝突で死んだアクターには通知しない
+            let (types_a, types_b) = match (self.actors.get(actor_a), self.actors.get(actor_b)) {
+                (Some(a), Some(b)) if a.is_active() && b.is_active() => {
+                    (a.component_types(), b.component_types())
+                }
+                _ => continue,
+            };
+
+            if let Some(a) = self.actors.get_mut(actor_a) {
+                let event = CollisionEvent::new(actor_b, collision.point, &types_b);
+                a.on_collision(actor_a, &event, &mut self.commands, &mut self.random);
+            }
+            if let Some(b) = self.actors.get_mut(actor_b) {
+                let event = CollisionEvent::new(actor_a, collision.point, &types_a);
+                b.on_collision(actor_b, &event, &mut self.commands, &mut self.random);
+            }
+        }
+    }
+
+    /// 更新中に予約されたアクターの生成・削除を適用し、死んだアクターを取り除く
+    fn apply_commands(&mut self) {
+        // 生成処理の中でさらに予約された命令も同じフレームで適用する
+        for _ in 0..MAX_COMMAND_PASSES {
+            if self.commands.is_empty() {
+                break;
+            }
+            for command in self.commands.take() {
+                match command {
+                    Command::Spawn(spawn) => spawn(self),
+                    Command::Despawn(actor_id) => self.remove_actor(actor_id),
+                    Command::Award(source) => self.award_score(source),
+                }
+            }
+        }
+        if !self.commands.is_empty() {
+            self.deferred_command_frames += 1;
+        }
+
+        let dead_actors: Vec<ActorId> = self
+            .actors
+            .iter()
+            .filter(|(_, actor)| actor.is_dead())
+            .map(|(actor_id, _)| actor_id)
+            .collect();
+        for actor_id in dead_actors {
+            self.remove_actor(actor_id);
+        }
+    }
+
+    /// 小惑星が全滅したら次のウェーブを始める
+    fn update_waves(&mut self) {
+        let asteroid_count = self.asteroid_count();
+        if let Some(params) = self.wave_manager.update(self.delta_time, asteroid_count) {
+            self.spawn_wave(params);
+            self.events.push(GameEvent::WaveStarted(self.wave()));
+        }
+    }
+
+    /// 画面の端に沿ってウェーブの小惑星を出現させる
+    fn spawn_wave(&mut self, params: WaveParams) {
+        let world_size = self.world_size;
+        let margin = 40.0;
+        for _ in 0..params.asteroid_count {
+            // 上下左右のどれかの端の帯の中に出す
+            let random = self.random.get(RandomStream::Spawning);
+            let pos = match random.int_range(0, 3) {
+                0 => random.vector_in_rect(0.0, world_size.x, 0.0, margin),
+                1 => random.vector_in_rect(0.0, world_size.x, world_size.y - margin, world_size.y),
+                2 => random.vector_in_rect(0.0, margin, 0.0, world_size.y),
+                _ => random.vector_in_rect(world_size.x - margin, world_size.x, 0.0, world_size.y),
+            };
+            let speed = random.float_range(params.min_speed, params.max_speed);
+            let velocity = random.vector_with_length(speed);
+            self.create_asteroid(pos, velocity, AsteroidSize::Large);
+        }
+    }
+
+    /// 残っている小惑星の数
+    pub fn asteroid_count(&self) -> usize {
+        self.actors
+            .iter()
+            .filter(|(_, actor)| !actor.is_dead() && actor.get_component::<Asteroid>().is_some())
+            .count()
+    }
+
+    /// 現在のウェーブ番号
+    pub fn wave(&self) -> u32 {
+        self.wave_manager.wave()
+    }
+
+    /// 宇宙船の破壊を検出して残機を減らし、必要なら復活させる
+    fn update_player(&mut self) {
+        let ship_alive = self
+            .player_id
+            .and_then(|id| self.actors.get(id))
+            .is_some_and(|actor| !actor.is_dead());
+        let spawn_area_clear = self.is_spawn_area_clear();
+        let Some(player) = &mut self.player else {
+            return;
+        };
+
+        let was_alive = player.state() == PlayerState::Alive;
+        let respawn = player.update(self.delta_time, ship_alive, spawn_area_clear);
+        if was_alive && !ship_alive {
+            self.events.push(GameEvent::ShipDestroyed);
+            if player.is_game_over() {
+                self.events.push(GameEvent::GameOver);
+            }
+        }
+        if respawn {
+            self.respawn_player();
+        }
+    }
+
+    /// 画面中央の周りに小惑星がいないか
+    fn is_spawn_area_clear(&self) -> bool {
+        let world_size = self.world_size;
+        let center = world_size * 0.5;
+        self.actors
+            .iter()
+            .filter(|(_, actor)| !actor.is_dead() && actor.get_component::<Asteroid>().is_some())
+            .all(|(_, actor)| {
+                let delta = actor.position() - center;
+                let delta = Vector2::new(
+                    Math::wrapped_delta(delta.x, world_size.x),
+                    Math::wrapped_delta(delta.y, world_size.y),
+                );
+                let radius = actor
+                    .get_component::<CircleComponent>()
+                    .map_or(0.0, |circle| circle.radius());
+                delta.length() > SPAWN_CLEAR_RADIUS + radius
+            })
+    }
+
+    /// 画面中央に宇宙船を作り直し、しばらく無敵にする
+    fn respawn_player(&mut self) {
+        let actor_id = self.add_actor();
+        self.setup_player_actor(actor_id);
+        let invulnerable_time = self
+            .player
+            .as_ref()
+            .map_or(0.0, |player| player.invulnerable_time());
+        let center = self.world_size * 0.5;
+        if let Some(actor) = self.actors.get_mut(actor_id) {
+            actor.set_position(center);
+            actor.set_rotation(-PI / 2.0);
+            if let Some(ship) = actor.get_component_mut::<Ship>() {
+                ship.set_invulnerable(invulnerable_time);
+            }
+        }
+    }
+
+    /// 得点を加え、しきい値を超えたら残機を増やす
+    pub fn award_score(&mut self, source: ScoreSource) {
+        // プレイヤーがいない世界や、ゲームオーバー後に残っていたレーザーの得点は数えない
+        let Some(player) = &mut self.player else {
+            return;
+        };
+        if player.is_game_over() {
+            return;
+        }
+        let extra_lives = self.score.award(source);
+        for _ in 0..extra_lives {
+            player.add_life();
+        }
+        // HUDに「EXTRA LIFE」を表示する
+        if extra_lives > 0 {
+            self.events.push(GameEvent::ExtraLife);
+        }
+    }
+
+    /// 前回取り出してから起きた出来事を取り出す
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// 得点
+    pub fn score(&self) -> u32 {
+        self.score.score()
+    }
+
+    /// 得点の設定を変えて得点を0からやり直す
+    pub fn set_score_config(&mut self, config: ScoreConfig) {
+        self.score = Score::new(config);
+    }
+
+    /// HUDなどに表示する現在の状態
+    pub fn stats(&self) -> GameStats {
+        GameStats {
+            score: self.score(),
+            lives: self.lives(),
+            wave: self.wave(),
+            game_over: self.is_game_over(),
+        }
+    }
+
+    /// 得点・残機・ウェーブとゲームの進行に関わるアクターの位置から作るチェックサム
+    ///
+    /// リプレイを再生したときに記録したときと同じ状態になったかを確かめるのに使う。
+    /// 破片や小惑星の向きなど見た目だけのものは含めないので、演出を変えても値は変わらない。
+    pub fn checksum(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u32(self.score());
+        hasher.write_u32(self.lives());
+        hasher.write_u32(self.wave());
+
+        let mut actors: Vec<(u64, &Actor)> = self
+            .actors
+            .iter()
+            .filter_map(|(_, actor)| Some((actor.spawn_order()?, actor)))
+            .collect();
+        actors.sort_by_key(|(spawn_order, _)| *spawn_order);
+        hasher.write_u64(actors.len() as u64);
+        for (spawn_order, actor) in actors {
+            let position = actor.position();
+            hasher.write_u64(spawn_order);
+            hasher.write_f32(position.x);
+            hasher.write_f32(position.y);
+            hasher.write_u8(actor.is_dead() as u8);
+        }
+        // 宇宙船の向きは次に撃つレーザーの向きになるので含める
+        if let Some(ship) = self.player_id().and_then(|id| self.actors.get(id)) {
+            hasher.write_f32(ship.rotation());
+        }
+        hasher.finish()
+    }
+
+    /// 残機
+    pub fn lives(&self) -> u32 {
+        self.player.as_ref().map_or(0, |player| player.lives())
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.player
+            .as_ref()
+            .is_some_and(|player| player.is_game_over())
+    }
+
+    pub fn player(&self) -> Option<&Player> {
+        self.player.as_ref()
+    }
+
+    /// プレイヤーの宇宙船のアクター（破壊されている間はNone）
+    pub fn player_id(&self) -> Option<ActorId> {
+        self.player_id.filter(|&id| self.actors.contains(id))
+    }
+
+    /// 画面（ゲーム空間）の大きさ
+    pub fn world_size(&self) -> Vector2 {
+        self.world_size
+    }
+
+    /// 画面の大きさを変更
+    pub fn set_world_size(&mut self, world_size: Vector2) {
+        self.world_size = world_size;
+        self.collision_system.set_world_size(Some(world_size));
+    }
+
+    /// 指定した用途のランダム値生成
+    pub fn random(&mut self, stream: RandomStream) -> &mut Random {
+        self.random.get(stream)
+    }
+
+    /// コンポーネントから予約されたスプライトのテクスチャ切り替えを反映
+    pub fn resolve_sprite_textures(&mut self, texture_manager: &mut TextureManager) {
+        for actor_id in self.sprites.iter() {
+            if let Some(sprite) = self
+                .actors
+                .get_mut(actor_id)
+                .and_then(|actor| actor.get_component_mut::<SpriteComponent>())
+            {
+                sprite.resolve_texture(texture_manager);
+            }
+        }
+    }
+
+    /// 衝突レイヤーの対応表
+    pub fn collision_layers(&self) -> &CollisionLayers {
+        &self.collision_layers
+    }
+
+    /// 衝突レイヤーの対応表を可変で取得（レイヤーの追加に使う）
+    pub fn collision_layers_mut(&mut self) -> &mut CollisionLayers {
+        &mut self.collision_layers
+    }
+
+    /// レイヤー名から衝突判定用の円を作成（未登録のレイヤー名があればエラー）
+    pub fn create_circle(
+        &self,
+        radius: f32,
+        layer: &str,
+        collides_with: &[&str],
+    ) -> Result<CircleComponent, String> {
+        Ok(CircleComponent::with_layers(
+            radius,
+            self.collision_layers.mask(&[layer])?,
+            self.collision_layers.mask(collides_with)?,
+        ))
+    }
+
+    /// レーザーを作成（velocityは発射した物体から引き継ぐ速度）
+    pub fn create_laser(&mut self, pos: Vector2, rot: f32, velocity: Vector2) -> ActorId {
+        let laser_id = self.add_actor();
+        if let Some(laser_actor) = self.actors.get_mut(laser_id) {
+            laser_actor.set_position(pos);
+            laser_actor.set_rotation(rot);
+            laser_actor.set_scale(Vector2::new(1.0, 1.0));
+        }
+        let sprite = SpriteComponent::new("Laser.png", DRAW_ORDER_LASER);
+        self.add_component(laser_id, Box::new(sprite));
+        let circle = self
+            .create_circle(11.0, "player_laser", &["asteroid"])
+            .expect("standard collision layers must be registered");
+        self.add_component(laser_id, Box::new(circle));
+        self.add_component(laser_id, Box::new(WrapComponent::new()));
+        self.add_component(laser_id, Box::new(Laser::new(velocity)));
+        laser_id
+    }
+
+    /// 指定した大きさの小惑星を作成
+    pub fn create_asteroid(
+        &mut self,
+        pos: Vector2,
+        velocity: Vector2,
+        size: AsteroidSize,
+    ) -> ActorId {
+        // 向きと回転の速さは見た目だけなので、ゲームの進行とは別の列から取る
+        let random = self.random.get(RandomStream::Vfx);
+        let rotation = random.float_range(0.0, PI * 2.0);
+        let rotation_speed = random.float_range(-PI / 2.0, PI / 2.0);
+        let asteroid_id = self.add_actor();
+        if let Some(asteroid_actor) = self.actors.get_mut(asteroid_id) {
+            asteroid_actor.set_position(pos);
+            asteroid_actor.set_rotation(rotation);
+            asteroid_actor.set_scale(Vector2::one() * size.scale());
+        }
+        let sprite = SpriteComponent::new("Asteroid.png", DRAW_ORDER_ASTEROID);
+        self.add_component(asteroid_id, Box::new(sprite));
+        let circle = self
+            .create_circle(
+                size.radius(),
+                "asteroid",
+                &["ship", "player_laser", "enemy_laser"],
+            )
+            .expect("standard collision layers must be registered");
+        self.add_component(asteroid_id, Box::new(circle));
+        self.add_component(asteroid_id, Box::new(WrapComponent::new()));
+        self.add_component(
+            asteroid_id,
+            Box::new(Asteroid::new(size, velocity, rotation_speed)),
+        );
+        asteroid_id
+    }
+
+    /// 指定した位置で爆発させ、破片を飛び散らせる
+    pub fn create_explosion(&mut self, pos: Vector2) {
+        for _ in 0..12 {
+            let random = self.random.get(RandomStream::Vfx);
+            let rotation = random.float_range(0.0, PI * 2.0);
+            let speed = random.float_range(60.0, 200.0);
+            let velocity = random.vector_with_length(speed);
+            let lifetime = random.float_range(0.4, 0.8);
+            let debris_id = self.add_effect_actor();
+            if let Some(debris_actor) = self.actors.get_mut(debris_id) {
+                debris_actor.set_position(pos);
+                debris_actor.set_rotation(rotation);
+                debris_actor.set_scale(Vector2::new(0.5, 0.5));
+            }
+            let sprite = SpriteComponent::new("Laser.png", DRAW_ORDER_LASER);
+            self.add_component(debris_id, Box::new(sprite));
+            self.add_component(debris_id, Box::new(Debris::new(velocity, lifetime)));
+        }
+    }
+
+    pub fn create_ship(&mut self) -> Ship {
+        Ship::new()
+    }
+
+    pub fn setup_player_actor(&mut self, actor_id: ActorId) {
+        if let Some(actor) = self.actors.get_mut(actor_id) {
+            actor.set_position(Vector2::new(100.0, 100.0));
+            actor.set_rotation(0.0);
+            actor.set_scale(Vector2::one());
+        } else {
+            return;
+        }
+        self.player_id = Some(actor_id);
+
+        let ship = Ship::new();
+        let sprite = SpriteComponent::new(SHIP_TEXTURE, DRAW_ORDER_SHIP);
+
+        // 質量1、推力400で加速し、キーを離すと抵抗でゆっくり止まる
+        let rigid_body = RigidBodyComponent::new(1.0, 0.5, 6.0, 400.0);
+        // 回転は角速度の抵抗と釣り合う約PI rad/sで頭打ちになる
+        let input_comp = InputComponent::new(400.0, 6.0 * PI);
+
+        self.add_component(actor_id, Box::new(sprite));
+        self.add_component(actor_id, Box::new(rigid_body));
+        self.add_component(actor_id, Box::new(input_comp));
+        let circle = self
+            .create_circle(20.0, "ship", &["asteroid", "enemy_laser", "pickup"])
+            .expect("standard collision layers must be registered");
+        self.add_component(actor_id, Box::new(circle));
+        self.add_component(actor_id, Box::new(WrapComponent::new()));
+        self.add_component(actor_id, Box::new(ship));
+    }
+}
@@ -0,0 +1,67 @@
+use super::component::{Component, ComponentContext};
+use super::math::Math;
+use super::vector2::Vector2;
+
+/// 画面端を越えたアクターを反対側に折り返す
+///
+/// 折り返し自体はWorldが全アクターの更新後に画面サイズを使って行う。
+/// このコンポーネントを持つアクターのスプライトは、画面端にかかっていると
+/// 反対側にも描画される。
+pub struct WrapComponent;
+
+impl Default for WrapComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WrapComponent {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 位置を画面内に折り返す
+    pub fn wrap_position(position: Vector2, world_size: Vector2) -> Vector2 {
+        Vector2::new(
+            Math::wrap(position.x, world_size.x),
+            Math::wrap(position.y, world_size.y),
+        )
+    }
+
+    /// 中心からextentの範囲が画面端にかかっているとき、
+    /// 反対側に描画するためのずらし量を返す
+    pub fn ghost_offsets(position: Vector2, extent: f32, world_size: Vector2) -> Vec<Vector2> {
+        let axis_offsets = |value: f32, size: f32| {
+            let mut offsets = vec![0.0];
+            if value - extent < 0.0 {
+                offsets.push(size);
+            }
+            if value + extent > size {
+                offsets.push(-size);
+            }
+            offsets
+        };
+
+        let mut offsets = Vec::new();
+        for &x in &axis_offsets(position.x, world_size.x) {
+            for &y in &axis_offsets(position.y, world_size.y) {
+                if x != 0.0 || y != 0.0 {
+                    offsets.push(Vector2::new(x, y));
+                }
+            }
+        }
+        offsets
+    }
+}
+
+impl Component for WrapComponent {
+    fn update(&mut self, _ctx: &mut ComponentContext, _delta_time: f32) {}
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -1,40 +1,134 @@
-use actor_game::actor::game::Game;
-use actor_game::actor::{
-    Actor, Vector2, Ship, InputComponent, MoveComponent,
-};
+use actor_game::actor::game::{Game, PlaybackResult};
+use actor_game::actor::{Replay, Vector2};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use winit::{
+    dpi::PhysicalSize,
     event::{Event, WindowEvent, ElementState, KeyboardInput},
     event_loop::{ControlFlow, EventLoop},
     window::WindowBuilder as WinitWindowBuilder,
 };
-use wgpu;
-use pollster;
-use std::f32::consts::PI;
 
-async fn init() -> (wgpu::Device, wgpu::Queue) {
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
-    let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions::default())
-        .await
-        .unwrap();
-    adapter.request_device(&wgpu::DeviceDescriptor::default(), None)
-        .await
-        .unwrap()
+/// コマンドライン引数
+///
+/// `--record <path>` でプレイの入力を記録し、`--replay <path>` で記録した入力を再生する。
+struct Options {
+    record_path: Option<PathBuf>,
+    replay_path: Option<PathBuf>,
+}
+
+impl Options {
+    fn parse() -> Self {
+        let mut options = Options {
+            record_path: None,
+            replay_path: None,
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--record" => options.record_path = args.next().map(PathBuf::from),
+                "--replay" => options.replay_path = args.next().map(PathBuf::from),
+                _ => println!("Unknown argument: {}", arg),
+            }
+        }
+        options
+    }
+}
+
+/// 記録中なら記録を終えてファイルに保存
+fn save_recording(game: &mut Game, path: &Option<PathBuf>) {
+    let (Some(path), Some(replay)) = (path, game.stop_recording()) else {
+        return;
+    };
+    if replay.is_full() {
+        println!("Replay reached the tick limit, later input was not recorded");
+    }
+    println!(
+        "Recorded {} ticks, checksum {:016x}",
+        replay.ticks().len(),
+        replay.checksum().unwrap_or_default()
+    );
+    match replay.save(path) {
+        Ok(()) => println!("Saved replay: {:?}", path),
+        Err(e) => println!("Failed to save replay {:?}: {}", path, e),
+    }
+}
+
+/// 再生を終えたときのチェックサムを記録と比べた結果を表示
+fn report_playback(result: PlaybackResult) {
+    match result.matches() {
+        Some(true) => println!("Replay finished: checksum {:016x} matches", result.checksum),
+        Some(false) => println!(
+            "Replay finished: checksum {:016x} does not match recorded {:016x}",
+            result.checksum,
+            result.recorded_checksum.unwrap_or_default()
+        ),
+        None => println!("Replay finished: checksum {:016x}", result.checksum),
+    }
 }
 
 fn main() {
+    let options = Options::parse();
+    let replay = options.replay_path.as_ref().map(|path| {
+        Replay::load(path).unwrap_or_else(|e| {
+            println!("Failed to load replay {:?}: {}", path, e);
+            std::process::exit(1);
+        })
+    });
+
     let event_loop = EventLoop::new();
-    let window = WinitWindowBuilder::new()
-        .with_title("Actor Game")
-        .build(&event_loop)
-        .unwrap();
+    let mut window_builder = WinitWindowBuilder::new().with_title("Actor Game");
+    if let Some(replay) = &replay {
+        // 記録したときと同じ画面の大きさで再生する
+        let size = replay.world_size();
+        window_builder =
+            window_builder.with_inner_size(PhysicalSize::new(size.x as u32, size.y as u32));
+    }
+    if replay.is_some() || options.record_path.is_some() {
+        window_builder = window_builder.with_resizable(false);
+    }
+    let window = window_builder.build(&event_loop).unwrap();
 
-    let mut game = Game::new(&window);
-    let actor_id = game.add_actor();
-    game.setup_player_actor(actor_id);
+    let mut game = match replay {
+        Some(replay) => {
+            println!(
+                "Playing back replay: seed {}, {} ticks",
+                replay.seed(),
+                replay.ticks().len()
+            );
+            let recorded_config_hash = replay.recorded_config_hash();
+            let game = Game::playback(&window, replay);
+            if recorded_config_hash != game.config_hash() {
+                println!(
+                    "Warning: replay was recorded with different settings (config {:016x}, now {:016x})",
+                    recorded_config_hash,
+                    game.config_hash()
+                );
+            }
+            game
+        }
+        None => Game::new(&window),
+    };
 
     let mut last_update_time = Instant::now();
     let target_frame_duration = Duration::from_secs_f32(1.0 / 60.0);
+    // シミュレーションは描画のフレームレートに関係なくこの間隔で進める
+    // （再生中は記録したときの間隔を使う）
+    if !game.is_playing_back() {
+        game.timestep_mut()
+            .set_step_time(target_frame_duration.as_secs_f32());
+    }
+    let record_path = options.record_path;
+    if record_path.is_some() {
+        match game.start_recording() {
+            Ok(()) => println!(
+                "Recording replay: seed {}, config {:016x}",
+                game.seed(),
+                game.config_hash()
+            ),
+            Err(e) => println!("Failed to start recording: {}", e),
+        }
+    }
 
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Poll;
@@ -54,12 +148,28 @@ fn main() {
                 let pressed = state == ElementState::Pressed;
                 game.handle_keyboard_input(keycode, pressed);
             }
+            Event::WindowEvent {
+                event: WindowEvent::Resized(size),
+                ..
+            } => {
+                let world_size = Vector2::new(size.width as f32, size.height as f32);
+                if (game.is_recording() || game.is_playing_back())
+                    && world_size != game.world_size()
+                {
+                    println!(
+                        "Ignoring resize to {}x{} while recording or playing back",
+                        size.width, size.height
+                    );
+                }
+                game.resize(size.width, size.height);
+            }
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
             } => {
                 *control_flow = ControlFlow::Exit;
                 game.shutdown();
+                save_recording(&mut game, &record_path);
             }
             Event::MainEventsCleared => {
                 let current_time = Instant::now();
@@ -68,6 +178,14 @@ fn main() {
 
                 game.set_delta_time(delta_time);
                 game.run();
+                if let Some(result) = game.take_playback_result() {
+                    report_playback(result);
+                }
+                if !game.is_running() {
+                    *control_flow = ControlFlow::Exit;
+                    save_recording(&mut game, &record_path);
+                    return;
+                }
 
                 window.request_redraw();
             }